@@ -16,6 +16,7 @@ use fugit::RateExtU32;
 use display_interface_spi::SPIInterface;
 use embedded_graphics::prelude::*;
 use embedded_graphics::{image::Image, pixelcolor::Rgb565};
+use embedded_hal_bus::spi::ExclusiveDevice;
 use tinybmp::Bmp;
 
 use bsp::hal::{
@@ -56,26 +57,28 @@ fn main() -> ! {
         &mut pac.RESETS,
     );
 
-    // These are implicitly used by the spi driver if they are in the correct mode
-    let _spi_sclk = pins.gpio10.into_mode::<gpio::FunctionSpi>();
-    let _spi_mosi = pins.gpio11.into_mode::<gpio::FunctionSpi>();
+    // These are used by the spi driver
+    let spi_sclk = pins.gpio10.into_function::<gpio::FunctionSpi>();
+    let spi_mosi = pins.gpio11.into_function::<gpio::FunctionSpi>();
     let spi_cs = pins.gpio9.into_push_pull_output();
 
     // Create an SPI driver instance for the SPI1 device
-    let spi = spi::Spi::<_, _, 8>::new(pac.SPI1);
+    let spi = spi::Spi::<_, _, _, 8>::new(pac.SPI1, (spi_mosi, spi_sclk));
 
     // Exchange the uninitialised SPI driver for an initialised one
     let spi = spi.init(
         &mut pac.RESETS,
         clocks.peripheral_clock.freq(),
         8_000_000u32.Hz(),
-        &embedded_hal::spi::MODE_0,
+        embedded_hal::spi::MODE_0,
     );
 
     let dc_pin = pins.gpio8.into_push_pull_output();
     let rst_pin = pins.gpio12.into_push_pull_output();
 
-    let spi_interface = SPIInterface::new(spi, dc_pin, spi_cs);
+    // The display is the only device on the bus
+    let spi_device = ExclusiveDevice::new_no_delay(spi, spi_cs);
+    let spi_interface = SPIInterface::new(spi_device, dc_pin);
 
     // initialize PWM for backlight
     let pwm_slices = pwm::Slices::new(pac.PWM, &mut pac.RESETS);
@@ -89,14 +92,12 @@ fn main() -> ! {
     let mut channel = pwm.channel_b;
     channel.output_to(pins.gpio13);
 
-    // Create display driver
-    let mut display = gc9a01a::GC9A01A::new(spi_interface, rst_pin, channel);
-    // Bring out of reset
-    display.reset(&mut delay).unwrap();
+    // Create display driver, bringing it out of reset and initializing its registers
+    let mut display = gc9a01a::Builder::new(spi_interface, rst_pin, channel)
+        .init(&mut delay)
+        .unwrap();
     // Turn on backlight
-    display.set_backlight(55000);
-    // Initialize registers
-    display.initialize(&mut delay).unwrap();
+    display.set_backlight(85).unwrap();
     // Clear the screen
     display.clear(Rgb565::BLACK).unwrap();
 
@@ -34,6 +34,7 @@ use embedded_graphics::{
     pixelcolor::Rgb565,
     primitives::{Circle, PrimitiveStyleBuilder, Rectangle, Triangle},
 };
+use embedded_hal_bus::spi::ExclusiveDevice;
 
 use bsp::hal::{
     clocks::{init_clocks_and_plls, Clock},
@@ -73,26 +74,28 @@ fn main() -> ! {
         &mut pac.RESETS,
     );
 
-    // These are implicitly used by the spi driver if they are in the correct mode
-    let _spi_sclk = pins.gpio10.into_mode::<gpio::FunctionSpi>();
-    let _spi_mosi = pins.gpio11.into_mode::<gpio::FunctionSpi>();
+    // These are used by the spi driver
+    let spi_sclk = pins.gpio10.into_function::<gpio::FunctionSpi>();
+    let spi_mosi = pins.gpio11.into_function::<gpio::FunctionSpi>();
     let spi_cs = pins.gpio9.into_push_pull_output();
 
     // Create an SPI driver instance for the SPI1 device
-    let spi = spi::Spi::<_, _, 8>::new(pac.SPI1);
+    let spi = spi::Spi::<_, _, _, 8>::new(pac.SPI1, (spi_mosi, spi_sclk));
 
     // Exchange the uninitialised SPI driver for an initialised one
     let spi = spi.init(
         &mut pac.RESETS,
         clocks.peripheral_clock.freq(),
         8_000_000u32.Hz(),
-        &embedded_hal::spi::MODE_0,
+        embedded_hal::spi::MODE_0,
     );
 
     let dc_pin = pins.gpio8.into_push_pull_output();
     let rst_pin = pins.gpio12.into_push_pull_output();
 
-    let spi_interface = SPIInterface::new(spi, dc_pin, spi_cs);
+    // The display is the only device on the bus
+    let spi_device = ExclusiveDevice::new_no_delay(spi, spi_cs);
+    let spi_interface = SPIInterface::new(spi_device, dc_pin);
 
     // initialize PWM for backlight
     let pwm_slices = pwm::Slices::new(pac.PWM, &mut pac.RESETS);
@@ -111,7 +114,7 @@ fn main() -> ! {
     // Bring out of reset
     display.reset(&mut delay).unwrap();
     // Turn on backlight
-    display.set_backlight(55000);
+    display.set_backlight(85).unwrap();
     // Initialize registers
     display.initialize(&mut delay).unwrap();
     // Fill screen with single color
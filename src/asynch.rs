@@ -0,0 +1,263 @@
+//! Async variant of the driver, for use with async executors such as embassy.
+//!
+//! Requires the `async` feature.
+
+use core::iter;
+use core::marker::PhantomData;
+
+use embedded_hal::digital::OutputPin;
+use embedded_hal_async::delay::DelayNs;
+
+use display_interface::{AsyncWriteOnlyDataCommand, DataFormat};
+
+use embedded_graphics_core::pixelcolor::Rgb565;
+use embedded_graphics_core::prelude::*;
+use embedded_graphics_core::primitives::Rectangle;
+
+use crate::graphics::ClippedColors;
+use crate::registers::*;
+use crate::state::State;
+use crate::{
+    Backlight, ColorOrder, DriverError, Error, Geometry, NoResetPin, Orientation, PixelFormat,
+};
+
+/// Async GC9A01A display driver.
+///
+/// Unlike the blocking [`GC9A01A`](crate::GC9A01A), this doesn't implement
+/// `DrawTarget`, since drawing through `embedded-graphics` is blocking.
+/// Render into a buffer instead and send it out using [`Self::fill_contiguous`].
+#[derive(Debug)]
+pub struct GC9A01A<DI, RST, BL, C = Rgb565> {
+    /// Display interface.
+    itf: DI,
+    /// Reset pin, if connected.
+    rst: Option<RST>,
+    /// Backlight control.
+    bl: BL,
+    /// Display configuration and panel state.
+    state: State,
+    /// Interface pixel format.
+    format: PhantomData<C>,
+}
+
+impl<DI, BL, C> GC9A01A<DI, NoResetPin, BL, C>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    BL: Backlight,
+    C: PixelFormat,
+{
+    /// Create a driver for a display without a dedicated reset pin.
+    ///
+    /// [`Self::reset`] issues a software reset instead.
+    pub fn new_without_reset(itf: DI, bl: BL) -> Self {
+        Self::with_optional_reset(itf, None, bl)
+    }
+}
+
+impl<DI, RST, BL, C> GC9A01A<DI, RST, BL, C>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    RST: OutputPin,
+    BL: Backlight,
+    C: PixelFormat,
+{
+    pub fn new(itf: DI, rst: RST, bl: BL) -> Self {
+        Self::with_optional_reset(itf, Some(rst), bl)
+    }
+
+    fn with_optional_reset(itf: DI, rst: Option<RST>, bl: BL) -> Self {
+        Self {
+            itf,
+            rst,
+            bl,
+            state: State::new(),
+            format: PhantomData,
+        }
+    }
+
+    pub async fn initialize<D>(&mut self, delay: &mut D) -> Result<(), DriverError<RST, BL>>
+    where
+        D: DelayNs,
+    {
+        self.initialize_with(&INIT_SEQ, delay).await
+    }
+
+    /// Initialize the display using a custom sequence,
+    /// see [`GC9A01A::initialize_with`](crate::GC9A01A::initialize_with).
+    pub async fn initialize_with<D>(
+        &mut self,
+        seq: &[InitOp],
+        delay: &mut D,
+    ) -> Result<(), DriverError<RST, BL>>
+    where
+        D: DelayNs,
+    {
+        for o in seq {
+            match *o {
+                InitOp::Cmd(c) => {
+                    self.write_command(c.cmd, c.data).await?;
+                    self.state.track_command(c.cmd, c.data);
+                }
+                InitOp::Delay(d) => delay.delay_ms(d).await,
+            }
+        }
+
+        self.write_command(GC9A01A_PIXFMT, &[C::COLMOD]).await?;
+        self.write_inversion().await?;
+        self.write_madctl().await
+    }
+
+    /// Reset the display, see [`GC9A01A::reset`](crate::GC9A01A::reset).
+    pub async fn reset<D>(&mut self, delay: &mut D) -> Result<(), DriverError<RST, BL>>
+    where
+        D: DelayNs,
+    {
+        if let Some(rst) = self.rst.as_mut() {
+            rst.set_high().map_err(Error::ResetPin)?;
+            delay.delay_ms(100).await;
+            rst.set_low().map_err(Error::ResetPin)?;
+            delay.delay_ms(100).await;
+            rst.set_high().map_err(Error::ResetPin)?;
+            delay.delay_ms(100).await;
+        } else {
+            self.write_command(GC9A01A_SWRESET, &[]).await?;
+            // Sleep Out may only be sent 120ms after a software reset
+            delay.delay_ms(120).await;
+        }
+        self.state.reset();
+        Ok(())
+    }
+
+    /// Release the display interface, reset pin and backlight,
+    /// see [`GC9A01A::release`](crate::GC9A01A::release).
+    pub fn release(self) -> (DI, Option<RST>, BL) {
+        (self.itf, self.rst, self.bl)
+    }
+
+    /// Temporarily borrow the display interface,
+    /// see [`GC9A01A::with_interface`](crate::GC9A01A::with_interface).
+    pub fn with_interface<R>(&mut self, f: impl FnOnce(&mut DI) -> R) -> R {
+        f(&mut self.itf)
+    }
+
+    /// Replace the display interface, returning the previous one,
+    /// see [`GC9A01A::replace_interface`](crate::GC9A01A::replace_interface).
+    pub fn replace_interface(&mut self, itf: DI) -> DI {
+        core::mem::replace(&mut self.itf, itf)
+    }
+
+    /// Configure the panel geometry.
+    pub fn set_geometry(&mut self, geometry: Geometry) {
+        self.state.geometry = geometry;
+    }
+
+    /// Change the display orientation.
+    pub async fn set_orientation(
+        &mut self,
+        orientation: Orientation,
+    ) -> Result<(), DriverError<RST, BL>> {
+        self.state.set_orientation(orientation)?;
+        self.write_madctl().await
+    }
+
+    /// Get the active display orientation.
+    pub fn orientation(&self) -> Orientation {
+        self.state.orientation
+    }
+
+    /// Set the color component order of the panel.
+    pub async fn set_color_order(
+        &mut self,
+        color_order: ColorOrder,
+    ) -> Result<(), DriverError<RST, BL>> {
+        self.state.color_order = color_order;
+        self.write_madctl().await
+    }
+
+    /// Turn display inversion on or off.
+    pub async fn set_inverted(&mut self, inverted: bool) -> Result<(), DriverError<RST, BL>> {
+        self.state.inverted = inverted;
+        self.write_inversion().await
+    }
+
+    /// Set the backlight brightness, from 0 (off) to 100 (full brightness).
+    pub fn set_backlight(&mut self, percent: u8) -> Result<(), DriverError<RST, BL>> {
+        self.bl.set_brightness(percent).map_err(Error::Backlight)
+    }
+
+    /// Fill the entire display with a single color.
+    pub async fn clear(&mut self, color: C) -> Result<(), DriverError<RST, BL>> {
+        let area = self.bounding_box();
+        self.fill_solid(&area, color).await
+    }
+
+    /// Fill an area with a single color.
+    pub async fn fill_solid(
+        &mut self,
+        area: &Rectangle,
+        color: C,
+    ) -> Result<(), DriverError<RST, BL>> {
+        let area = area.intersection(&self.bounding_box());
+        let pixels = area.size.width as usize * area.size.height as usize;
+        self.fill_contiguous(&area, iter::repeat_n(color, pixels))
+            .await
+    }
+
+    /// Fill an area with colors in row-major order, e.g. to flush a frame buffer.
+    ///
+    /// Colors that fall outside of the display are skipped. Like the blocking driver,
+    /// this fails with [`Error::InvalidState`] while the panel is in sleep mode, i.e.
+    /// until it has been initialized.
+    pub async fn fill_contiguous<I>(
+        &mut self,
+        area: &Rectangle,
+        colors: I,
+    ) -> Result<(), DriverError<RST, BL>>
+    where
+        I: IntoIterator<Item = C>,
+    {
+        let drawable_area = area.intersection(&self.bounding_box());
+        let Some(bottom_right) = drawable_area.bottom_right() else {
+            return Ok(());
+        };
+
+        let (caset, paset) = self.state.address_window(
+            drawable_area.top_left.x as u16,
+            bottom_right.x as u16,
+            drawable_area.top_left.y as u16,
+            bottom_right.y as u16,
+        )?;
+        self.write_command(GC9A01A_CASET, &caset).await?;
+        self.write_command(GC9A01A_PASET, &paset).await?;
+        self.itf
+            .send_commands(DataFormat::U8(&[GC9A01A_RAMWR]))
+            .await?;
+
+        let pixels = drawable_area.size.width as usize * drawable_area.size.height as usize;
+        let colors = ClippedColors::new(colors.into_iter(), area, &drawable_area).take(pixels);
+        C::send_pixels_async(&mut self.itf, colors).await?;
+        Ok(())
+    }
+
+    async fn write_command(&mut self, cmd: u8, data: &[u8]) -> Result<(), DriverError<RST, BL>> {
+        self.itf.send_commands(DataFormat::U8(&[cmd])).await?;
+        self.itf.send_data(DataFormat::U8(data)).await?;
+        Ok(())
+    }
+
+    async fn write_madctl(&mut self) -> Result<(), DriverError<RST, BL>> {
+        self.write_command(GC9A01A_MADCTL, &[self.state.madctl()])
+            .await
+    }
+
+    async fn write_inversion(&mut self) -> Result<(), DriverError<RST, BL>> {
+        self.write_command(self.state.inversion_command(), &[])
+            .await
+    }
+}
+
+impl<DI, RST, BL, C> OriginDimensions for GC9A01A<DI, RST, BL, C> {
+    fn size(&self) -> Size {
+        self.state.size()
+    }
+}
@@ -0,0 +1,74 @@
+use core::convert::Infallible;
+
+use embedded_hal::digital::OutputPin;
+use embedded_hal::pwm::SetDutyCycle;
+
+/// Backlight control, taking a brightness in percent.
+///
+/// Implemented for PWM channels, for on/off control through a GPIO with [`GpioBacklight`],
+/// for displays without backlight control with [`NoBacklight`], and for external LED
+/// drivers with [`FnBacklight`].
+pub trait Backlight {
+    type Error;
+
+    /// Set the brightness, from 0 (off) to 100 (full brightness).
+    fn set_brightness(&mut self, percent: u8) -> Result<(), Self::Error>;
+}
+
+impl<P> Backlight for P
+where
+    P: SetDutyCycle,
+{
+    type Error = P::Error;
+
+    fn set_brightness(&mut self, percent: u8) -> Result<(), Self::Error> {
+        self.set_duty_cycle_percent(percent.min(100))
+    }
+}
+
+/// Backlight switched on and off through a GPIO.
+///
+/// Any non-zero brightness turns the backlight on.
+#[derive(Debug)]
+pub struct GpioBacklight<P>(pub P);
+
+impl<P> Backlight for GpioBacklight<P>
+where
+    P: OutputPin,
+{
+    type Error = P::Error;
+
+    fn set_brightness(&mut self, percent: u8) -> Result<(), Self::Error> {
+        if percent > 0 {
+            self.0.set_high()
+        } else {
+            self.0.set_low()
+        }
+    }
+}
+
+/// Placeholder for displays whose backlight can't be controlled.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoBacklight;
+
+impl Backlight for NoBacklight {
+    type Error = Infallible;
+
+    fn set_brightness(&mut self, _percent: u8) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Backlight controlled by a closure, e.g. driving an external LED driver.
+pub struct FnBacklight<F>(pub F);
+
+impl<F, E> Backlight for FnBacklight<F>
+where
+    F: FnMut(u8) -> Result<(), E>,
+{
+    type Error = E;
+
+    fn set_brightness(&mut self, percent: u8) -> Result<(), Self::Error> {
+        (self.0)(percent.min(100))
+    }
+}
@@ -0,0 +1,205 @@
+use core::convert::Infallible;
+
+use embedded_hal::digital::OutputPin;
+
+use display_interface::{DataFormat, WriteOnlyDataCommand};
+
+use embedded_graphics_core::pixelcolor::raw::RawU16;
+use embedded_graphics_core::pixelcolor::Rgb565;
+use embedded_graphics_core::prelude::*;
+use embedded_graphics_core::primitives::Rectangle;
+
+use crate::graphics::ClippedColors;
+use crate::{Backlight, DriverError, GC9A01A};
+
+/// Draw target for one band of rows, passed to the closure of [`GC9A01A::draw_banded`].
+///
+/// The band uses the coordinates and size of the whole display, drawing outside of the
+/// band's rows is discarded.
+#[derive(Debug)]
+pub struct Band<'a> {
+    buf: &'a mut [u16],
+    /// Rows of the display covered by this band.
+    area: Rectangle,
+    /// Size of the whole display.
+    size: Size,
+}
+
+impl Band<'_> {
+    /// The rows of the display covered by this band.
+    ///
+    /// Drawing code can use this to skip parts of the scene that don't intersect the band.
+    pub fn area(&self) -> Rectangle {
+        self.area
+    }
+
+    fn set_pixel(&mut self, p: Point, color: Rgb565) {
+        let p = p - self.area.top_left;
+        let i = p.y as usize * self.size.width as usize + p.x as usize;
+        self.buf[i] = color.into_storage().to_be();
+    }
+}
+
+impl OriginDimensions for Band<'_> {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl DrawTarget for Band<'_> {
+    type Color = Rgb565;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(coord, color) in pixels.into_iter() {
+            if self.area.contains(coord) {
+                self.set_pixel(coord, color);
+            }
+        }
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let drawable_area = area.intersection(&self.area);
+        if drawable_area.is_zero_sized() {
+            return Ok(());
+        }
+
+        let colors = ClippedColors::new(colors.into_iter(), area, &drawable_area);
+
+        for (p, color) in drawable_area.points().zip(colors) {
+            self.set_pixel(p, color);
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let drawable_area = area.intersection(&self.area);
+        let raw = RawU16::from(color).into_inner().to_be();
+        let stride = self.size.width as usize;
+        let x = drawable_area.top_left.x as usize;
+        let width = drawable_area.size.width as usize;
+
+        for y in drawable_area.rows() {
+            let start = (y - self.area.top_left.y) as usize * stride + x;
+            self.buf[start..start + width].fill(raw);
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.buf.fill(RawU16::from(color).into_inner().to_be());
+        Ok(())
+    }
+}
+
+impl<DI, RST, BL> GC9A01A<DI, RST, BL, Rgb565>
+where
+    DI: WriteOnlyDataCommand,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    /// Render a scene band by band, for when a full [`FrameBuffer`](crate::FrameBuffer)
+    /// doesn't fit in RAM.
+    ///
+    /// `buf` holds as many full-width rows as fit, e.g. 240 * 16 pixels for bands of 16
+    /// rows. For each band, `draw` is called to draw the whole scene, which is then sent
+    /// to the display before the next band is rendered. Each band starts out black, so
+    /// the result is the same as drawing into a cleared frame buffer.
+    ///
+    /// ```ignore
+    /// let mut buf = [0; 240 * 16];
+    /// display.draw_banded(&mut buf, |band| {
+    ///     band.clear(Rgb565::BLUE)?;
+    ///     Circle::new(Point::new(60, 60), 120)
+    ///         .into_styled(PrimitiveStyle::with_fill(Rgb565::RED))
+    ///         .draw(band)
+    /// })?;
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `buf` is smaller than a single row.
+    pub fn draw_banded<F>(
+        &mut self,
+        buf: &mut [u16],
+        mut draw: F,
+    ) -> Result<(), DriverError<RST, BL>>
+    where
+        F: FnMut(&mut Band<'_>) -> Result<(), Infallible>,
+    {
+        let size = self.bounding_box().size;
+        let width = size.width as usize;
+        let rows = (buf.len() / width).min(size.height as usize) as u32;
+        assert!(rows > 0, "band buffer smaller than one row");
+
+        let mut top = 0;
+        while top < size.height {
+            let height = rows.min(size.height - top);
+            let area = Rectangle::new(Point::new(0, top as i32), Size::new(size.width, height));
+            let pixels = &mut buf[..width * height as usize];
+
+            pixels.fill(0);
+            let Ok(()) = draw(&mut Band {
+                buf: pixels,
+                area,
+                size,
+            });
+
+            let mut start = 0;
+            self.for_each_window(&area, |display, n| {
+                display
+                    .itf
+                    .send_data(DataFormat::U16(&pixels[start..start + n]))?;
+                start += n;
+                Ok(())
+            })?;
+
+            top += height;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::pixelcolor::raw::RawU16;
+    use embedded_graphics_core::pixelcolor::Rgb565;
+    use embedded_graphics_core::prelude::*;
+    use embedded_graphics_core::primitives::Rectangle;
+
+    use crate::mock;
+
+    /// Two images, one of them partly off-screen, and a rectangle.
+    fn scene<D: DrawTarget<Color = Rgb565>>(target: &mut D) -> Result<(), D::Error> {
+        for top_left in [(20, 30), (200, 180)] {
+            let area = Rectangle::new(Point::from(top_left), Size::new(50, 70));
+            let colors = (0..50 * 70).map(|i| RawU16::new(i as u16 + 1).into());
+            target.fill_contiguous(&area, colors)?;
+        }
+        target.fill_solid(
+            &Rectangle::new(Point::new(100, 5), Size::new(30, 200)),
+            Rgb565::RED,
+        )
+    }
+
+    #[test]
+    fn draw_banded_matches_direct() {
+        let mut direct = mock::display();
+        direct.clear(Rgb565::BLACK).unwrap();
+        scene(&mut direct).unwrap();
+
+        let mut banded = mock::display();
+        let mut buf = [0; 240 * 16];
+        banded.draw_banded(&mut buf, |band| scene(band)).unwrap();
+
+        assert!(direct.release().0.gram == banded.release().0.gram);
+    }
+}
@@ -0,0 +1,152 @@
+use core::marker::PhantomData;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+use display_interface::WriteOnlyDataCommand;
+
+use embedded_graphics_core::pixelcolor::Rgb565;
+
+use crate::registers::{InitOp, INIT_SEQ};
+use crate::{
+    Backlight, ColorOrder, DriverError, Geometry, NoResetPin, Orientation, PixelFormat, GC9A01A,
+};
+
+/// Builder for a [`GC9A01A`], which resets and initializes the display in the right order.
+///
+/// ```ignore
+/// let mut display = Builder::new(spi_interface, rst_pin, backlight)
+///     .orientation(Orientation::Landscape)
+///     .inverted(false)
+///     .init(&mut delay)?;
+/// display.set_backlight(100)?;
+/// ```
+#[derive(Debug)]
+pub struct Builder<'a, DI, RST, BL, C = Rgb565> {
+    itf: DI,
+    rst: Option<RST>,
+    bl: BL,
+    geometry: Geometry,
+    orientation: Orientation,
+    color_order: ColorOrder,
+    inverted: bool,
+    init_seq: &'a [InitOp],
+    format: PhantomData<C>,
+}
+
+impl<'a, DI, BL> Builder<'a, DI, NoResetPin, BL, Rgb565>
+where
+    DI: WriteOnlyDataCommand,
+    BL: Backlight,
+{
+    /// Start building a driver for a display without a dedicated reset pin.
+    pub fn without_reset(itf: DI, bl: BL) -> Self {
+        Self::with_optional_reset(itf, None, bl)
+    }
+}
+
+impl<'a, DI, RST, BL> Builder<'a, DI, RST, BL, Rgb565>
+where
+    DI: WriteOnlyDataCommand,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    pub fn new(itf: DI, rst: RST, bl: BL) -> Self {
+        Self::with_optional_reset(itf, Some(rst), bl)
+    }
+
+    fn with_optional_reset(itf: DI, rst: Option<RST>, bl: BL) -> Self {
+        Self {
+            itf,
+            rst,
+            bl,
+            geometry: Geometry::default(),
+            orientation: Orientation::default(),
+            color_order: ColorOrder::default(),
+            inverted: true,
+            init_seq: &INIT_SEQ,
+            format: PhantomData,
+        }
+    }
+}
+
+impl<'a, DI, RST, BL, C> Builder<'a, DI, RST, BL, C>
+where
+    DI: WriteOnlyDataCommand,
+    RST: OutputPin,
+    BL: Backlight,
+    C: PixelFormat,
+{
+    /// Set the display orientation.
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Set the color component order of the panel.
+    pub fn color_order(mut self, color_order: ColorOrder) -> Self {
+        self.color_order = color_order;
+        self
+    }
+
+    /// Turn display inversion on or off.
+    pub fn inverted(mut self, inverted: bool) -> Self {
+        self.inverted = inverted;
+        self
+    }
+
+    /// Set the panel geometry, including its offsets.
+    pub fn geometry(mut self, geometry: Geometry) -> Self {
+        self.geometry = geometry;
+        self
+    }
+
+    /// Set the graphics RAM column and row of the top-left visible pixel.
+    pub fn offset(mut self, col_offset: u16, row_offset: u16) -> Self {
+        self.geometry = self.geometry.with_offset(col_offset, row_offset);
+        self
+    }
+
+    /// Use a custom initialization sequence, e.g. built with
+    /// [`InitSequence`](crate::InitSequence).
+    pub fn init_sequence(mut self, init_seq: &'a [InitOp]) -> Self {
+        self.init_seq = init_seq;
+        self
+    }
+
+    /// Select the interface pixel format through the color type.
+    pub fn pixel_format<C2>(self) -> Builder<'a, DI, RST, BL, C2>
+    where
+        C2: PixelFormat,
+    {
+        Builder {
+            itf: self.itf,
+            rst: self.rst,
+            bl: self.bl,
+            geometry: self.geometry,
+            orientation: self.orientation,
+            color_order: self.color_order,
+            inverted: self.inverted,
+            init_seq: self.init_seq,
+            format: PhantomData,
+        }
+    }
+
+    /// Reset and initialize the display, returning the ready to use driver.
+    ///
+    /// The backlight is left untouched.
+    pub fn init<D>(self, delay: &mut D) -> Result<GC9A01A<DI, RST, BL, C>, DriverError<RST, BL>>
+    where
+        D: DelayNs,
+    {
+        let mut display = GC9A01A::with_optional_reset(self.itf, self.rst, self.bl);
+        display.state.geometry = self.geometry;
+        display.state.orientation = self.orientation;
+        display.state.color_order = self.color_order;
+        display.state.inverted = self.inverted;
+
+        display.reset(delay)?;
+        display.initialize_with(self.init_seq, delay)?;
+        Ok(display)
+    }
+}
@@ -0,0 +1,26 @@
+use core::convert::Infallible;
+
+use display_interface::DisplayError;
+
+/// Error returned by the driver.
+///
+/// `RstE` and `BlE` are the error types of the reset pin and the backlight.
+#[derive(Debug, Clone)]
+pub enum Error<RstE = Infallible, BlE = Infallible> {
+    /// Communication with the display failed.
+    Interface(DisplayError),
+    /// Driving the reset pin failed.
+    ResetPin(RstE),
+    /// Setting the backlight failed.
+    Backlight(BlE),
+    /// The requested area or value lies outside of the display.
+    OutOfBounds,
+    /// The operation is not possible in the current state, e.g. drawing while asleep.
+    InvalidState,
+}
+
+impl<RstE, BlE> From<DisplayError> for Error<RstE, BlE> {
+    fn from(e: DisplayError) -> Self {
+        Error::Interface(e)
+    }
+}
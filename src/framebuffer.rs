@@ -0,0 +1,261 @@
+use core::convert::Infallible;
+
+use embedded_hal::digital::OutputPin;
+
+use display_interface::{DataFormat, WriteOnlyDataCommand};
+
+use embedded_graphics_core::pixelcolor::raw::RawU16;
+use embedded_graphics_core::pixelcolor::Rgb565;
+use embedded_graphics_core::prelude::*;
+use embedded_graphics_core::primitives::Rectangle;
+
+use crate::graphics::ClippedColors;
+use crate::{Backlight, DriverError, GC9A01A};
+
+/// Maximum number of separate dirty rectangles, before they are merged.
+const DIRTY_RECTS: usize = 4;
+
+/// RGB565 frame buffer in RAM, for drawing without visible overdraw.
+///
+/// Draw the whole scene into the frame buffer, then call [`Self::flush`] to send the
+/// changed regions to the display. The buffer holds the pixels in the display's byte order.
+#[derive(Debug)]
+pub struct FrameBuffer<'a> {
+    buf: &'a mut [u16],
+    size: Size,
+    /// Regions changed since the last flush.
+    dirty: [Option<Rectangle>; DIRTY_RECTS],
+}
+
+impl<'a> FrameBuffer<'a> {
+    /// Create a frame buffer for the default 240x240 display.
+    ///
+    /// # Panics
+    ///
+    /// If `buf` holds less than 240 * 240 pixels.
+    pub fn new(buf: &'a mut [u16]) -> Self {
+        Self::with_size(buf, Size::new(240, 240))
+    }
+
+    /// Create a frame buffer of the given size.
+    ///
+    /// # Panics
+    ///
+    /// If `buf` is too small for `size`.
+    pub fn with_size(buf: &'a mut [u16], size: Size) -> Self {
+        let len = size.width as usize * size.height as usize;
+        assert!(buf.len() >= len, "frame buffer too small");
+        Self {
+            buf: &mut buf[..len],
+            size,
+            dirty: [None; DIRTY_RECTS],
+        }
+    }
+
+    /// Send the regions changed since the last flush to the display.
+    ///
+    /// To avoid tearing, call [`GC9A01A::wait_for_vsync`] first.
+    pub fn flush<DI, RST, BL>(
+        &mut self,
+        display: &mut GC9A01A<DI, RST, BL, Rgb565>,
+    ) -> Result<(), DriverError<RST, BL>>
+    where
+        DI: WriteOnlyDataCommand,
+        RST: OutputPin,
+        BL: Backlight,
+    {
+        let stride = self.size.width as usize;
+        let display_area = display.bounding_box();
+
+        for i in 0..DIRTY_RECTS {
+            let Some(area) = self.dirty[i] else {
+                continue;
+            };
+            let area = area.intersection(&display_area);
+            let x = area.top_left.x as usize;
+            let width = area.size.width as usize;
+            let mut y = area.top_left.y as usize;
+
+            let buf = &*self.buf;
+            display.for_each_window(&area, |display, pixels| {
+                let rows = pixels / width;
+                if width == stride {
+                    // Full rows are contiguous in the buffer
+                    let start = y * stride;
+                    display
+                        .itf
+                        .send_data(DataFormat::U16(&buf[start..start + pixels]))?;
+                } else {
+                    for row in y..y + rows {
+                        let start = row * stride + x;
+                        display
+                            .itf
+                            .send_data(DataFormat::U16(&buf[start..start + width]))?;
+                    }
+                }
+                y += rows;
+                Ok(())
+            })?;
+
+            self.dirty[i] = None;
+        }
+
+        Ok(())
+    }
+
+    /// Mark the whole frame buffer as changed, e.g. after the display was reset.
+    pub fn invalidate(&mut self) {
+        self.dirty = [None; DIRTY_RECTS];
+        self.dirty[0] = Some(self.bounding_box());
+    }
+
+    fn set_pixel(&mut self, p: Point, color: Rgb565) {
+        let i = p.y as usize * self.size.width as usize + p.x as usize;
+        self.buf[i] = color.into_storage().to_be();
+    }
+
+    fn mark_dirty(&mut self, area: Rectangle) {
+        if area.is_zero_sized() {
+            return;
+        }
+
+        // Grow an overlapping rectangle
+        for r in self.dirty.iter_mut().flatten() {
+            if !r.intersection(&area).is_zero_sized() {
+                *r = envelope(r, &area);
+                return;
+            }
+        }
+
+        if let Some(slot) = self.dirty.iter_mut().find(|r| r.is_none()) {
+            *slot = Some(area);
+            return;
+        }
+
+        // Out of rectangles, merge into the one that grows the least
+        let grown = |r: &Rectangle| {
+            let e = envelope(r, &area);
+            e.size.width * e.size.height - r.size.width * r.size.height
+        };
+        if let Some(r) = self.dirty.iter_mut().flatten().min_by_key(|r| grown(r)) {
+            *r = envelope(r, &area);
+        }
+    }
+}
+
+/// Smallest rectangle containing both `a` and `b`, which must not be zero sized.
+fn envelope(a: &Rectangle, b: &Rectangle) -> Rectangle {
+    let (Some(a_br), Some(b_br)) = (a.bottom_right(), b.bottom_right()) else {
+        return *a;
+    };
+    Rectangle::with_corners(
+        a.top_left.component_min(b.top_left),
+        a_br.component_max(b_br),
+    )
+}
+
+impl OriginDimensions for FrameBuffer<'_> {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl DrawTarget for FrameBuffer<'_> {
+    type Color = Rgb565;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bounding_box = self.bounding_box();
+        let mut min = Point::new(i32::MAX, i32::MAX);
+        let mut max = Point::new(i32::MIN, i32::MIN);
+
+        for Pixel(coord, color) in pixels.into_iter() {
+            if bounding_box.contains(coord) {
+                self.set_pixel(coord, color);
+                min = min.component_min(coord);
+                max = max.component_max(coord);
+            }
+        }
+
+        if min.x <= max.x {
+            self.mark_dirty(Rectangle::with_corners(min, max));
+        }
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let drawable_area = area.intersection(&self.bounding_box());
+        if drawable_area.is_zero_sized() {
+            return Ok(());
+        }
+
+        let colors = ClippedColors::new(colors.into_iter(), area, &drawable_area);
+
+        for (p, color) in drawable_area.points().zip(colors) {
+            self.set_pixel(p, color);
+        }
+
+        self.mark_dirty(drawable_area);
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let drawable_area = area.intersection(&self.bounding_box());
+        let raw = RawU16::from(color).into_inner().to_be();
+        let stride = self.size.width as usize;
+        let x = drawable_area.top_left.x as usize;
+        let width = drawable_area.size.width as usize;
+
+        for y in drawable_area.rows() {
+            let start = y as usize * stride + x;
+            self.buf[start..start + width].fill(raw);
+        }
+
+        self.mark_dirty(drawable_area);
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.buf.fill(RawU16::from(color).into_inner().to_be());
+        self.invalidate();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::pixelcolor::raw::RawU16;
+    use embedded_graphics_core::pixelcolor::Rgb565;
+    use embedded_graphics_core::prelude::*;
+    use embedded_graphics_core::primitives::Rectangle;
+
+    use super::FrameBuffer;
+    use crate::mock;
+
+    #[test]
+    fn flush_matches_direct() {
+        let mut buf = [0; 240 * 240];
+        let mut fb = FrameBuffer::new(&mut buf);
+        let mut direct = mock::display();
+        let mut display = mock::display();
+
+        for top_left in [(10, 300), (-100, 10), (20, 30), (200, 180)] {
+            let area = Rectangle::new(Point::from(top_left), Size::new(50, 70));
+            let colors = || (0..50 * 70).map(|i| RawU16::new(i as u16).into());
+            fb.fill_contiguous(&area, colors()).unwrap();
+            direct.fill_contiguous(&area, colors()).unwrap();
+        }
+        let pixels = [Pixel(Point::new(239, 0), Rgb565::RED)];
+        fb.draw_iter(pixels).unwrap();
+        direct.draw_iter(pixels).unwrap();
+        fb.flush(&mut display).unwrap();
+
+        assert!(direct.release().0.gram == display.release().0.gram);
+    }
+}
@@ -0,0 +1,124 @@
+/// Gamma curve, as set with SET_GAMMA1/2 (F0h/F1h) for the positive polarity
+/// or SET_GAMMA3/4 (F2h/F3h) for the negative polarity.
+///
+/// The fields are named after the datasheet's bitfields. The `vrN` fields select the
+/// reference voltage for grayscale level N, `dig2j0`/`dig2j1` fine-tune the
+/// lowest levels. Values are truncated to the width of their bitfield.
+///
+/// The controller applies the same curve to the red, green and blue subpixels, so the
+/// presets can only change contrast. There is no warm (or cool) preset, since shifting
+/// the white point needs separate curves per channel; reduce the blue component of the
+/// drawn colors instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GammaCurve {
+    /// 2 bits
+    pub dig2j0: u8,
+    /// 6 bits
+    pub vr1: u8,
+    /// 2 bits
+    pub dig2j1: u8,
+    /// 6 bits
+    pub vr2: u8,
+    /// 5 bits
+    pub vr4: u8,
+    /// 5 bits
+    pub vr6: u8,
+    /// 4 bits
+    pub vr0: u8,
+    /// 4 bits
+    pub vr13: u8,
+    /// 7 bits
+    pub vr20: u8,
+    /// 7 bits
+    pub vr43: u8,
+    /// 3 bits
+    pub vr27: u8,
+    /// 5 bits
+    pub vr57: u8,
+    /// 3 bits
+    pub vr36: u8,
+    /// 5 bits
+    pub vr59: u8,
+    /// 6 bits
+    pub vr61: u8,
+    /// 6 bits
+    pub vr62: u8,
+    /// 4 bits
+    pub vr50: u8,
+    /// 4 bits
+    pub vr63: u8,
+}
+
+impl GammaCurve {
+    /// The curve used by the default initialization sequence.
+    pub const DEFAULT: Self = Self::decode(
+        [0x45, 0x09, 0x08, 0x08, 0x26, 0x2A],
+        [0x43, 0x70, 0x72, 0x36, 0x37, 0x6F],
+    );
+
+    /// Steeper mid-tones than the default, for more contrast.
+    pub const HIGH_CONTRAST: Self = Self {
+        vr20: 0x22,
+        vr43: 0x4B,
+        ..Self::DEFAULT
+    };
+
+    /// Flatter mid-tones than the default, for softer images.
+    pub const SOFT: Self = Self {
+        vr20: 0x32,
+        vr43: 0x3B,
+        ..Self::DEFAULT
+    };
+
+    /// Decode the parameters of SET_GAMMA1 and SET_GAMMA2 (or SET_GAMMA3 and SET_GAMMA4).
+    pub const fn decode(first: [u8; 6], second: [u8; 6]) -> Self {
+        Self {
+            dig2j0: first[0] >> 6,
+            vr1: first[0] & 0x3F,
+            dig2j1: first[1] >> 6,
+            vr2: first[1] & 0x3F,
+            vr4: first[2] & 0x1F,
+            vr6: first[3] & 0x1F,
+            vr0: first[4] & 0x0F,
+            vr13: first[4] >> 4,
+            vr20: first[5] & 0x7F,
+            vr43: second[0] & 0x7F,
+            vr27: second[1] >> 5,
+            vr57: second[1] & 0x1F,
+            vr36: second[2] >> 5,
+            vr59: second[2] & 0x1F,
+            vr61: second[3] & 0x3F,
+            vr62: second[4] & 0x3F,
+            vr50: second[5] >> 4,
+            vr63: second[5] & 0x0F,
+        }
+    }
+
+    /// Encode the parameters of SET_GAMMA1 and SET_GAMMA2 (or SET_GAMMA3 and SET_GAMMA4).
+    pub const fn encode(&self) -> ([u8; 6], [u8; 6]) {
+        (
+            [
+                (self.dig2j0 & 0x03) << 6 | (self.vr1 & 0x3F),
+                (self.dig2j1 & 0x03) << 6 | (self.vr2 & 0x3F),
+                self.vr4 & 0x1F,
+                self.vr6 & 0x1F,
+                (self.vr13 & 0x0F) << 4 | (self.vr0 & 0x0F),
+                self.vr20 & 0x7F,
+            ],
+            [
+                self.vr43 & 0x7F,
+                (self.vr27 & 0x07) << 5 | (self.vr57 & 0x1F),
+                (self.vr36 & 0x07) << 5 | (self.vr59 & 0x1F),
+                self.vr61 & 0x3F,
+                self.vr62 & 0x3F,
+                (self.vr50 & 0x0F) << 4 | (self.vr63 & 0x0F),
+            ],
+        )
+    }
+}
+
+impl Default for GammaCurve {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
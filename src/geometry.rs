@@ -0,0 +1,174 @@
+use embedded_graphics_core::geometry::Size;
+
+use crate::Orientation;
+
+/// Panel geometry, in the native (portrait) orientation.
+///
+/// Some modules only expose part of the controller's graphics RAM, in which case the
+/// visible area starts at `col_offset`/`row_offset`. For landscape orientations the
+/// offsets are swapped along with the width and height. Orientations that mirror the
+/// columns or rows (MX/MY) address graphics RAM from the other end, which is accounted
+/// for using the graphics RAM size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Geometry {
+    /// Visible width in pixels.
+    pub width: u16,
+    /// Visible height in pixels.
+    pub height: u16,
+    /// First graphics RAM column of the visible area.
+    pub col_offset: u16,
+    /// First graphics RAM row of the visible area.
+    pub row_offset: u16,
+    /// Number of graphics RAM columns of the controller.
+    pub gram_width: u16,
+    /// Number of graphics RAM rows of the controller.
+    pub gram_height: u16,
+}
+
+impl Geometry {
+    /// Geometry of a panel that maps the visible area at the start of graphics RAM.
+    pub const fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            col_offset: 0,
+            row_offset: 0,
+            gram_width: 240,
+            gram_height: 240,
+        }
+    }
+
+    /// Set the graphics RAM column and row of the top-left visible pixel.
+    pub const fn with_offset(mut self, col_offset: u16, row_offset: u16) -> Self {
+        self.col_offset = col_offset;
+        self.row_offset = row_offset;
+        self
+    }
+
+    /// Set the size of the controller's graphics RAM, 240x240 by default.
+    pub const fn with_gram_size(mut self, gram_width: u16, gram_height: u16) -> Self {
+        self.gram_width = gram_width;
+        self.gram_height = gram_height;
+        self
+    }
+
+    /// Visible size in the given orientation.
+    pub(crate) fn size(&self, orientation: Orientation) -> Size {
+        let width = u32::from(self.width);
+        let height = u32::from(self.height);
+        if orientation.is_landscape() {
+            Size::new(height, width)
+        } else {
+            Size::new(width, height)
+        }
+    }
+
+    /// Graphics RAM row of the first visible row, as addressed in the given orientation.
+    fn address_row_offset(&self, orientation: Orientation) -> Option<u16> {
+        if orientation.mirrors_rows() {
+            self.gram_height
+                .checked_sub(self.height)?
+                .checked_sub(self.row_offset)
+        } else {
+            Some(self.row_offset)
+        }
+    }
+
+    /// Graphics RAM column of the first visible column, as addressed in the given
+    /// orientation.
+    fn address_col_offset(&self, orientation: Orientation) -> Option<u16> {
+        if orientation.mirrors_columns() {
+            self.gram_width
+                .checked_sub(self.width)?
+                .checked_sub(self.col_offset)
+        } else {
+            Some(self.col_offset)
+        }
+    }
+
+    /// CASET and PASET parameters for a window in display coordinates.
+    ///
+    /// Returns `None` if the window doesn't fit in graphics RAM.
+    pub(crate) fn address_window(
+        &self,
+        orientation: Orientation,
+        xs: u16,
+        xe: u16,
+        ys: u16,
+        ye: u16,
+    ) -> Option<([u8; 4], [u8; 4])> {
+        let col_offset = self.address_col_offset(orientation)?;
+        let row_offset = self.address_row_offset(orientation)?;
+        let (x_offset, y_offset, x_limit, y_limit) = if orientation.is_landscape() {
+            (row_offset, col_offset, self.gram_height, self.gram_width)
+        } else {
+            (col_offset, row_offset, self.gram_width, self.gram_height)
+        };
+
+        let xe = xe.checked_add(x_offset).filter(|&xe| xe < x_limit)?;
+        let ye = ye.checked_add(y_offset).filter(|&ye| ye < y_limit)?;
+        let [xs_hi, xs_lo] = xs.checked_add(x_offset)?.to_be_bytes();
+        let [xe_hi, xe_lo] = xe.to_be_bytes();
+        let [ys_hi, ys_lo] = ys.checked_add(y_offset)?.to_be_bytes();
+        let [ye_hi, ye_lo] = ye.to_be_bytes();
+
+        Some(([xs_hi, xs_lo, xe_hi, xe_lo], [ys_hi, ys_lo, ye_hi, ye_lo]))
+    }
+}
+
+impl Default for Geometry {
+    /// The round 240x240 panel the GC9A01A is usually paired with.
+    fn default() -> Self {
+        Self::new(240, 240)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Geometry;
+    use crate::Orientation;
+
+    /// Start of the CASET and PASET ranges for display pixel (0, 0).
+    fn origin(geometry: &Geometry, orientation: Orientation) -> (u16, u16) {
+        let (caset, paset) = geometry.address_window(orientation, 0, 0, 0, 0).unwrap();
+        (
+            u16::from_be_bytes([caset[0], caset[1]]),
+            u16::from_be_bytes([paset[0], paset[1]]),
+        )
+    }
+
+    #[test]
+    fn offsets_follow_mirroring() {
+        let geometry = Geometry::new(128, 128).with_offset(2, 1);
+        assert_eq!(origin(&geometry, Orientation::PortraitMirrored), (2, 1));
+        assert_eq!(origin(&geometry, Orientation::Portrait), (110, 1));
+        assert_eq!(origin(&geometry, Orientation::PortraitFlipped), (2, 111));
+        assert_eq!(origin(&geometry, Orientation::Landscape), (1, 2));
+        assert_eq!(origin(&geometry, Orientation::LandscapeFlipped), (111, 110));
+    }
+
+    #[test]
+    fn window_outside_gram() {
+        let geometry = Geometry::new(240, 240).with_offset(0, 10);
+        assert!(geometry
+            .address_window(Orientation::PortraitFlipped, 0, 0, 0, 0)
+            .is_none());
+
+        let geometry = Geometry::new(240, 240).with_offset(u16::MAX, 0);
+        assert!(geometry
+            .address_window(Orientation::PortraitMirrored, 1, 1, 0, 0)
+            .is_none());
+
+        // Not mirrored, but running past the end of graphics RAM
+        let geometry = Geometry::new(240, 240).with_offset(10, 0);
+        assert!(geometry
+            .address_window(Orientation::PortraitMirrored, 235, 239, 0, 0)
+            .is_none());
+        assert!(geometry
+            .address_window(Orientation::Landscape, 0, 0, 235, 239)
+            .is_none());
+        assert!(geometry
+            .address_window(Orientation::PortraitMirrored, 0, 229, 0, 239)
+            .is_some());
+    }
+}
@@ -1,38 +1,57 @@
-use crate::GC9A01A;
+use crate::{Backlight, DriverError, PixelFormat, GC9A01A};
 
-use core::ops::Range;
+use embedded_hal::digital::OutputPin;
 
-use embedded_hal::digital::v2::OutputPin;
-use embedded_hal::PwmPin;
-
-use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+use display_interface::{DataFormat, WriteOnlyDataCommand};
 
 use embedded_graphics_core::prelude::*;
-use embedded_graphics_core::{pixelcolor::Rgb565, primitives::Rectangle};
+use embedded_graphics_core::primitives::Rectangle;
+
+/// Maximum number of pixels buffered by `draw_iter` before they are sent out.
+const SPAN_PIXELS: usize = 64;
+
+/// Size of the buffer used by `fill_solid` and `clear`.
+const FILL_BUFFER_BYTES: usize = 64;
 
-impl<DI, RST, PWM> DrawTarget for GC9A01A<DI, RST, PWM>
+impl<DI, RST, BL, C> DrawTarget for GC9A01A<DI, RST, BL, C>
 where
     DI: WriteOnlyDataCommand,
     RST: OutputPin,
-    PWM: PwmPin,
+    BL: Backlight,
+    C: PixelFormat,
 {
-    type Color = Rgb565;
-    type Error = DisplayError;
+    type Color = C;
+    type Error = DriverError<RST, BL>;
 
     fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
     where
         I: IntoIterator<Item = Pixel<Self::Color>>,
     {
+        let bounding_box = self.bounding_box();
+
+        // Runs of horizontally adjacent pixels are sent using a single address window
+        let mut span = [C::BLACK; SPAN_PIXELS];
+        let mut span_len = 0;
+        let mut span_start = Point::zero();
+
         for Pixel(coord, color) in pixels.into_iter() {
-            // TODO: get rid of hardcoded window size
-            if let Ok((x @ 0..=240, y @ 0..=240)) = coord.try_into() {
-                let x = u8::try_from(x).unwrap();
-                let y = u8::try_from(y).unwrap();
-                self.draw_bytes(x, x, y, y, &color.into_storage().to_be_bytes())?;
+            if !bounding_box.contains(coord) {
+                continue;
             }
+
+            let adjacent = span_len < SPAN_PIXELS
+                && coord.y == span_start.y
+                && coord.x == span_start.x + span_len as i32;
+            if !adjacent {
+                self.draw_span(span_start, &span[..span_len])?;
+                span_start = coord;
+                span_len = 0;
+            }
+            span[span_len] = color;
+            span_len += 1;
         }
 
-        Ok(())
+        self.draw_span(span_start, &span[..span_len])
     }
 
     fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
@@ -41,48 +60,286 @@ where
     {
         // Clamp area to drawable part of the display target
         let drawable_area = area.intersection(&self.bounding_box());
+        if drawable_area.is_zero_sized() {
+            return Ok(());
+        }
 
-        // Check that there are visible pixels to be drawn
-        if drawable_area.size != Size::zero() {
-            let Range {
-                start: x_start,
-                end: x_end,
-            } = drawable_area.columns();
-            let Range {
-                start: y_start,
-                end: y_end,
-            } = drawable_area.rows();
-            self.draw_color(
-                u8::try_from(x_start).unwrap(),
-                u8::try_from(x_end - 1).unwrap(),
-                u8::try_from(y_start).unwrap(),
-                u8::try_from(y_end - 1).unwrap(),
-                &mut area
-                    .points()
-                    .zip(colors)
-                    .filter(|(pos, _color)| drawable_area.contains(*pos))
-                    .map(|(_, color)| color.into_storage()),
-            )
+        let mut colors = ClippedColors::new(colors.into_iter(), area, &drawable_area);
+
+        self.for_each_window(&drawable_area, |display, pixels| {
+            C::send_pixels(&mut display.itf, colors.by_ref().take(pixels))?;
+            Ok(())
+        })
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.fill_solid_buffered::<FILL_BUFFER_BYTES>(area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.fill_solid(&self.bounding_box(), color)
+    }
+}
+
+impl<DI, RST, BL, C> GC9A01A<DI, RST, BL, C>
+where
+    DI: WriteOnlyDataCommand,
+    RST: OutputPin,
+    BL: Backlight,
+    C: PixelFormat,
+{
+    /// Fill an area with a single color, streaming it from an `N` byte stack buffer.
+    ///
+    /// Larger buffers need fewer, longer transfers, which increases throughput on most
+    /// interfaces. [`DrawTarget::fill_solid`] and [`DrawTarget::clear`] use a 64 byte buffer.
+    pub fn fill_solid_buffered<const N: usize>(
+        &mut self,
+        area: &Rectangle,
+        color: C,
+    ) -> Result<(), DriverError<RST, BL>> {
+        let drawable_area = area.intersection(&self.bounding_box());
+
+        // Fill the buffer with as many whole pixels as fit
+        let mut buf = [0; N];
+        let mut pixel = [0; 3];
+        color.encode(&mut pixel);
+        let pixel = &pixel[..C::BYTES_PER_PIXEL];
+        let chunk_pixels = (N / C::BYTES_PER_PIXEL).max(1);
+        let chunk = if N >= C::BYTES_PER_PIXEL {
+            for bytes in buf.chunks_exact_mut(C::BYTES_PER_PIXEL) {
+                bytes.copy_from_slice(pixel);
+            }
+            &buf[..chunk_pixels * C::BYTES_PER_PIXEL]
         } else {
+            pixel
+        };
+
+        self.for_each_window(&drawable_area, |display, mut pixels| {
+            while pixels > 0 {
+                let n = pixels.min(chunk_pixels);
+                display
+                    .itf
+                    .send_data(DataFormat::U8(&chunk[..n * C::BYTES_PER_PIXEL]))?;
+                pixels -= n;
+            }
             Ok(())
+        })
+    }
+
+    /// Set the address window for each band of rows of `area` that is contiguous in
+    /// graphics RAM, and let `send` write the given number of pixels to it.
+    ///
+    /// Without scrolling, the whole area is a single band.
+    pub(crate) fn for_each_window<F>(
+        &mut self,
+        area: &Rectangle,
+        mut send: F,
+    ) -> Result<(), DriverError<RST, BL>>
+    where
+        F: FnMut(&mut Self, usize) -> Result<(), DriverError<RST, BL>>,
+    {
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
+        };
+
+        let x_start = u16::try_from(area.top_left.x).unwrap();
+        let x_end = u16::try_from(bottom_right.x).unwrap();
+        let y_end = u16::try_from(bottom_right.y).unwrap();
+        let width = usize::from(x_end - x_start + 1);
+
+        // Split the area where rows are not contiguous in graphics RAM while scrolled
+        let mut y = u16::try_from(area.top_left.y).unwrap();
+        loop {
+            let (physical, run_end) = self.map_row(y);
+            let run_end = run_end.min(y_end);
+            let rows = run_end - y + 1;
+            self.set_windows(x_start, x_end, physical, physical + rows - 1)?;
+            send(self, width * usize::from(rows))?;
+            if run_end == y_end {
+                return Ok(());
+            }
+            y = run_end + 1;
         }
     }
 
-    // Keep the default `fill_solid` implementation that calls `fill_continguous`
-    // fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error>;
+    /// Draw a horizontal run of pixels, starting at `start`.
+    fn draw_span(&mut self, start: Point, colors: &[C]) -> Result<(), DriverError<RST, BL>> {
+        if colors.is_empty() {
+            return Ok(());
+        }
 
-    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
-        self.set_windows(0, Self::WIDTH - 1, 0, Self::HEIGHT - 1)?;
-        let size = Self::WIDTH as usize * Self::HEIGHT as usize;
-        self.itf.send_data(DataFormat::U8Iter(
-            &mut (0..size).flat_map(|_| color.into_storage().to_be_bytes()),
-        ))
+        let x = u16::try_from(start.x).unwrap();
+        let y = self.physical_row(u16::try_from(start.y).unwrap());
+        let x_end = x + (colors.len() - 1) as u16;
+        self.draw_color(x, x_end, y, y, colors.iter().copied())
     }
 }
 
-impl<DI, RST, PWM> OriginDimensions for GC9A01A<DI, RST, PWM> {
+/// Row-major colors of an area, reduced to the part inside a clipping rectangle.
+///
+/// Clipped pixels are skipped using [`Iterator::nth`], so whole rows above and pixels
+/// left and right of the clipping rectangle are passed over without being inspected.
+pub(crate) struct ClippedColors<I> {
+    colors: I,
+    /// Number of visible pixels per row.
+    visible: usize,
+    /// Number of clipped pixels between the end of one row and the start of the next.
+    row_skip: usize,
+    /// Visible pixels left in the current row.
+    remaining: usize,
+    /// Clipped pixels to skip before the next visible pixel.
+    pending: usize,
+}
+
+impl<I> ClippedColors<I> {
+    /// `clip` must lie within `area`, or be zero sized in which case no colors are returned.
+    pub(crate) fn new(colors: I, area: &Rectangle, clip: &Rectangle) -> Self {
+        let width = area.size.width as usize;
+        let visible = if clip.is_zero_sized() {
+            0
+        } else {
+            clip.size.width as usize
+        };
+        let offset = clip.top_left - area.top_left;
+        Self {
+            colors,
+            visible,
+            row_skip: width.saturating_sub(visible),
+            remaining: visible,
+            pending: usize::try_from(offset.y).unwrap_or(0) * width
+                + usize::try_from(offset.x).unwrap_or(0),
+        }
+    }
+}
+
+impl<I> Iterator for ClippedColors<I>
+where
+    I: Iterator,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.visible == 0 {
+            return None;
+        }
+        if self.remaining == 0 {
+            self.remaining = self.visible;
+            self.pending = self.row_skip;
+        }
+        self.remaining -= 1;
+
+        match core::mem::take(&mut self.pending) {
+            0 => self.colors.next(),
+            skip => self.colors.nth(skip),
+        }
+    }
+}
+
+impl<DI, RST, BL, C> OriginDimensions for GC9A01A<DI, RST, BL, C> {
     fn size(&self) -> Size {
-        // TODO: get rid of hardcoded window size
-        Size::new(240, 240)
+        self.state.size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use embedded_graphics_core::pixelcolor::raw::RawU16;
+    use embedded_graphics_core::pixelcolor::Rgb565;
+    use embedded_graphics_core::prelude::*;
+    use embedded_graphics_core::primitives::Rectangle;
+
+    use crate::mock;
+    use crate::registers::{GC9A01A_CASET, GC9A01A_PASET, GC9A01A_RAMWR};
+
+    /// Colors numbering the pixels of an area in row-major order.
+    fn numbered(area: &Rectangle) -> impl Iterator<Item = Rgb565> {
+        (0..area.size.width * area.size.height).map(|i| RawU16::new(i as u16).into())
+    }
+
+    /// Column and row ranges of every memory write, which must set both.
+    fn windows(itf: &mock::MockInterface) -> Vec<((u16, u16), (u16, u16))> {
+        let range = |p: &[u8]| {
+            (
+                u16::from_be_bytes([p[0], p[1]]),
+                u16::from_be_bytes([p[2], p[3]]),
+            )
+        };
+        itf.commands
+            .windows(3)
+            .filter(|w| w[2].0 == GC9A01A_RAMWR)
+            .map(|w| {
+                assert_eq!((w[0].0, w[1].0), (GC9A01A_CASET, GC9A01A_PASET));
+                (range(&w[0].1), range(&w[1].1))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn draw_iter_coalesces_runs() {
+        let mut display = mock::display();
+        // A run crossing the span limit, followed by a single pixel on the next row
+        let run = (10..110).map(|x| Pixel(Point::new(x, 5), Rgb565::RED));
+        let single = Pixel(Point::new(10, 6), Rgb565::RED);
+        display.draw_iter(run.chain([single])).unwrap();
+
+        let itf = display.release().0;
+        assert_eq!(
+            windows(&itf),
+            [((10, 73), (5, 5)), ((74, 109), (5, 5)), ((10, 10), (6, 6))]
+        );
+    }
+
+    #[test]
+    fn draw_iter_scrolled_row() {
+        let mut display = mock::display();
+        display.set_scroll_area(10, 10).unwrap();
+        display.set_scroll_offset(50).unwrap();
+        let run = (0..30).map(|x| Pixel(Point::new(x, 20), Rgb565::RED));
+        display.draw_iter(run).unwrap();
+
+        let itf = display.release().0;
+        // Display row 20 shows graphics RAM row 70
+        assert_eq!(windows(&itf), [((0, 29), (70, 70))]);
+        let red = RawU16::from(Rgb565::RED).into_inner();
+        assert!((0..30).all(|x| itf.pixel(x, 70) == red));
+    }
+
+    #[test]
+    fn fill_contiguous_off_screen() {
+        let mut display = mock::display();
+        for top_left in [(10, 300), (-100, 10), (300, 10), (10, -100)] {
+            let area = Rectangle::new(Point::from(top_left), Size::new(20, 20));
+            display.fill_contiguous(&area, numbered(&area)).unwrap();
+        }
+
+        let itf = display.release().0;
+        assert!(itf.commands.iter().all(|(cmd, _)| *cmd != GC9A01A_RAMWR));
+        assert!(itf.gram.iter().all(|&p| p == mock::UNWRITTEN));
+    }
+
+    #[test]
+    fn fill_contiguous_clipped() {
+        for top_left in [(-5, 100), (230, 100), (100, -5), (100, 230)] {
+            let mut display = mock::display();
+            let area = Rectangle::new(Point::from(top_left), Size::new(20, 20));
+            display.fill_contiguous(&area, numbered(&area)).unwrap();
+
+            let visible = area.intersection(&display.bounding_box());
+            let itf = display.release().0;
+            for (i, p) in area.points().enumerate() {
+                if visible.contains(p) {
+                    assert_eq!(itf.pixel(p.x as usize, p.y as usize), i as u16);
+                }
+            }
+            let drawn = itf.gram.iter().filter(|&&p| p != mock::UNWRITTEN).count();
+            assert_eq!(
+                drawn,
+                visible.size.width as usize * visible.size.height as usize
+            );
+        }
     }
 }
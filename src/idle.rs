@@ -0,0 +1,88 @@
+use embedded_graphics_core::pixelcolor::RgbColor;
+use embedded_graphics_core::prelude::*;
+use embedded_graphics_core::primitives::Rectangle;
+
+/// Draw target adapter that reduces colors to the 8 colors shown in idle mode.
+///
+/// In idle mode the display only uses the most significant bit of each color channel.
+/// Drawing through this adapter applies the same reduction, so that e.g. dithering
+/// or anti-aliasing can be avoided up front and the UI looks as designed.
+#[derive(Debug)]
+pub struct IdleColors<'a, T> {
+    target: &'a mut T,
+}
+
+impl<'a, T> IdleColors<'a, T>
+where
+    T: DrawTarget,
+    T::Color: RgbColor,
+{
+    pub fn new(target: &'a mut T) -> Self {
+        Self { target }
+    }
+}
+
+/// Reduce a color to the nearest of the 8 colors shown in idle mode.
+pub fn idle_color<C>(color: C) -> C
+where
+    C: RgbColor,
+{
+    let r = color.r() > C::MAX_R / 2;
+    let g = color.g() > C::MAX_G / 2;
+    let b = color.b() > C::MAX_B / 2;
+    match (r, g, b) {
+        (false, false, false) => C::BLACK,
+        (false, false, true) => C::BLUE,
+        (false, true, false) => C::GREEN,
+        (false, true, true) => C::CYAN,
+        (true, false, false) => C::RED,
+        (true, false, true) => C::MAGENTA,
+        (true, true, false) => C::YELLOW,
+        (true, true, true) => C::WHITE,
+    }
+}
+
+impl<T> Dimensions for IdleColors<'_, T>
+where
+    T: DrawTarget,
+{
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<T> DrawTarget for IdleColors<'_, T>
+where
+    T: DrawTarget,
+    T::Color: RgbColor,
+{
+    type Color = T::Color;
+    type Error = T::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(coord, color)| Pixel(coord, idle_color(color))),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.target
+            .fill_contiguous(area, colors.into_iter().map(idle_color))
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.fill_solid(area, idle_color(color))
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.clear(idle_color(color))
+    }
+}
@@ -0,0 +1,184 @@
+use crate::registers::*;
+
+/// Initialization sequence builder, starting from the default sequence.
+///
+/// This allows adapting the power and gamma settings to panels from different vendors,
+/// without having to copy the whole sequence. `N` is the maximum number of steps.
+///
+/// The orientation and color order (MADCTL), pixel format (COLMOD) and inversion
+/// (INVON/INVOFF) are always written from the driver's state after the sequence, so
+/// changing them here has no effect. Use e.g. [`Builder::inverted`](crate::Builder::inverted)
+/// or [`GC9A01A::set_inverted`](crate::GC9A01A::set_inverted) instead.
+///
+/// ```ignore
+/// let seq = InitSequence::<64>::new().set(0xEB, &[0x10]);
+/// // Don't invert colors
+/// display.set_inverted(false)?;
+/// display.initialize_with(seq.as_slice(), &mut delay)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct InitSequence<const N: usize = 64> {
+    ops: [InitOp; N],
+    len: usize,
+}
+
+impl<const N: usize> InitSequence<N> {
+    /// Start from the default initialization sequence.
+    ///
+    /// # Panics
+    ///
+    /// If `N` is smaller than the length of the default sequence.
+    pub fn new() -> Self {
+        let mut seq = Self::empty();
+        for op in INIT_SEQ {
+            seq = seq.push(op);
+        }
+        seq
+    }
+
+    /// Start from an empty initialization sequence.
+    pub fn empty() -> Self {
+        Self {
+            ops: [InitOp::Delay(0); N],
+            len: 0,
+        }
+    }
+
+    /// Append a step to the end of the sequence.
+    ///
+    /// # Panics
+    ///
+    /// If the sequence already holds `N` steps.
+    pub fn push(mut self, op: InitOp) -> Self {
+        assert!(self.len < N, "initialization sequence is full");
+        self.ops[self.len] = op;
+        self.len += 1;
+        self
+    }
+
+    /// Override the parameters of every command with opcode `cmd`.
+    ///
+    /// If the sequence doesn't contain the command yet, it is inserted before Sleep Out,
+    /// so it takes effect before the panel is started.
+    ///
+    /// # Panics
+    ///
+    /// If the command has to be inserted and the sequence already holds `N` steps.
+    pub fn set(mut self, cmd: u8, data: &'static [u8]) -> Self {
+        let mut found = false;
+        for op in self.ops[..self.len].iter_mut() {
+            if let InitOp::Cmd(c) = op {
+                if c.cmd == cmd {
+                    c.data = data;
+                    found = true;
+                }
+            }
+        }
+        if found {
+            return self;
+        }
+
+        let at = self.position(GC9A01A_SLPOUT).unwrap_or(self.len);
+        self.insert(at, InitOp::Cmd(InitCmd { cmd, data }))
+    }
+
+    /// Remove every command with opcode `cmd`.
+    pub fn remove(mut self, cmd: u8) -> Self {
+        let mut kept = 0;
+        for i in 0..self.len {
+            let op = self.ops[i];
+            if !matches!(op, InitOp::Cmd(c) if c.cmd == cmd) {
+                self.ops[kept] = op;
+                kept += 1;
+            }
+        }
+        self.len = kept;
+        self
+    }
+
+    /// The steps of the sequence, to be passed to
+    /// [`GC9A01A::initialize_with`](crate::GC9A01A::initialize_with).
+    pub fn as_slice(&self) -> &[InitOp] {
+        &self.ops[..self.len]
+    }
+
+    fn position(&self, cmd: u8) -> Option<usize> {
+        self.as_slice()
+            .iter()
+            .position(|op| matches!(op, InitOp::Cmd(c) if c.cmd == cmd))
+    }
+
+    fn insert(mut self, at: usize, op: InitOp) -> Self {
+        assert!(self.len < N, "initialization sequence is full");
+        self.ops.copy_within(at..self.len, at + 1);
+        self.ops[at] = op;
+        self.len += 1;
+        self
+    }
+}
+
+impl<const N: usize> Default for InitSequence<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InitSequence;
+    use crate::registers::*;
+
+    fn cmd(cmd: u8, data: &'static [u8]) -> InitOp {
+        InitOp::Cmd(InitCmd { cmd, data })
+    }
+
+    /// Two writes of 0xEB around an inversion command, then Sleep Out and Display On.
+    fn sequence() -> InitSequence<8> {
+        InitSequence::empty()
+            .push(cmd(0xEB, &[0x14]))
+            .push(cmd(GC9A01A_INVON, &[]))
+            .push(cmd(0xEB, &[0x14]))
+            .push(cmd(GC9A01A_SLPOUT, &[]))
+            .push(InitOp::Delay(120))
+            .push(cmd(GC9A01A_DISPON, &[]))
+    }
+
+    #[test]
+    fn set_existing_command() {
+        let seq = sequence().set(0xEB, &[0x10]);
+        assert_eq!(seq.as_slice().len(), 6);
+        assert_eq!(seq.as_slice()[0], cmd(0xEB, &[0x10]));
+        assert_eq!(seq.as_slice()[2], cmd(0xEB, &[0x10]));
+    }
+
+    #[test]
+    fn set_new_command_before_sleep_out() {
+        let seq = sequence().set(0xC3, &[0x13]);
+        assert_eq!(
+            seq.as_slice(),
+            [
+                cmd(0xEB, &[0x14]),
+                cmd(GC9A01A_INVON, &[]),
+                cmd(0xEB, &[0x14]),
+                cmd(0xC3, &[0x13]),
+                cmd(GC9A01A_SLPOUT, &[]),
+                InitOp::Delay(120),
+                cmd(GC9A01A_DISPON, &[]),
+            ]
+        );
+    }
+
+    #[test]
+    fn remove_every_match() {
+        let seq = sequence().remove(0xEB);
+        assert_eq!(
+            seq.as_slice(),
+            [
+                cmd(GC9A01A_INVON, &[]),
+                cmd(GC9A01A_SLPOUT, &[]),
+                InitOp::Delay(120),
+                cmd(GC9A01A_DISPON, &[]),
+            ]
+        );
+    }
+}
@@ -1,49 +1,158 @@
 //! Library for the GC9A01A display driver
 #![no_std]
 
+#[cfg(feature = "async")]
+pub mod asynch;
+mod backlight;
+mod band;
+mod builder;
+mod error;
+mod framebuffer;
+mod gamma;
+mod geometry;
 mod graphics;
+mod idle;
+mod init;
+#[cfg(test)]
+mod mock;
+mod orientation;
+mod pixel_format;
+mod read;
 mod registers;
+mod scroll;
+mod state;
+mod tearing;
+mod timing;
 
-use embedded_hal::blocking::delay;
-use embedded_hal::digital::v2::OutputPin;
-use embedded_hal::PwmPin;
+pub use backlight::{Backlight, FnBacklight, GpioBacklight, NoBacklight};
+pub use band::Band;
+pub use builder::Builder;
+pub use error::Error;
+pub use framebuffer::FrameBuffer;
+pub use gamma::GammaCurve;
+pub use geometry::Geometry;
+pub use idle::{idle_color, IdleColors};
+pub use init::InitSequence;
+pub use orientation::{ColorOrder, Orientation};
+pub use pixel_format::PixelFormat;
+pub use read::{DisplayId, PowerMode, ReadDataCommand, SpiReadInterface};
+pub use registers::{InitCmd, InitOp, INIT_SEQ};
+pub use tearing::{TePin, TearingEffect, VsyncWait};
+pub use timing::{FrameRate, Inversion};
 
-use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+use core::convert::Infallible;
+use core::marker::PhantomData;
+use core::ops::Range;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{ErrorType, OutputPin};
+
+use display_interface::{DataFormat, WriteOnlyDataCommand};
+
+use embedded_graphics_core::pixelcolor::Rgb565;
 
 use registers::*;
+use scroll::ScrollArea;
+use state::State;
 
+/// Error type of a driver with reset pin `RST` and backlight `BL`.
+type DriverError<RST, BL> = Error<<RST as ErrorType>::Error, <BL as Backlight>::Error>;
+
+/// Placeholder for boards where the reset line of the display is not connected to a GPIO.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoResetPin;
+
+impl ErrorType for NoResetPin {
+    type Error = Infallible;
+}
+
+impl OutputPin for NoResetPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// GC9A01A display driver.
+///
+/// The color type `C` selects the interface pixel format, see [`PixelFormat`].
 #[derive(Debug)]
-pub struct GC9A01A<DI, RST, PWM> {
+pub struct GC9A01A<DI, RST, BL, C = Rgb565> {
     /// Display interface.
     itf: DI,
-    /// Reset pin.
-    rst: RST,
-    /// Backlight pin, pulse-width modulated.
-    bl: PWM,
+    /// Reset pin, if connected.
+    rst: Option<RST>,
+    /// Backlight control.
+    bl: BL,
+    /// Display configuration and panel state.
+    state: State,
+    /// Interface pixel format.
+    format: PhantomData<C>,
+}
+
+impl<DI, BL, C> GC9A01A<DI, NoResetPin, BL, C>
+where
+    DI: WriteOnlyDataCommand,
+    BL: Backlight,
+    C: PixelFormat,
+{
+    /// Create a driver for a display without a dedicated reset pin,
+    /// e.g. when it is tied to the MCU reset line.
+    ///
+    /// [`Self::reset`] issues a software reset instead.
+    pub fn new_without_reset(itf: DI, bl: BL) -> Self {
+        Self::with_optional_reset(itf, None, bl)
+    }
 }
 
-impl<DI, RST, PWM> GC9A01A<DI, RST, PWM>
+impl<DI, RST, BL, C> GC9A01A<DI, RST, BL, C>
 where
     DI: WriteOnlyDataCommand,
     RST: OutputPin,
-    PWM: PwmPin,
+    BL: Backlight,
+    C: PixelFormat,
 {
-    pub const WIDTH: u8 = 240;
-    pub const HEIGHT: u8 = 240;
+    pub fn new(itf: DI, rst: RST, bl: BL) -> Self {
+        Self::with_optional_reset(itf, Some(rst), bl)
+    }
 
-    pub fn new(itf: DI, rst: RST, bl: PWM) -> Self {
-        Self { itf, rst, bl }
+    fn with_optional_reset(itf: DI, rst: Option<RST>, bl: BL) -> Self {
+        Self {
+            itf,
+            rst,
+            bl,
+            state: State::new(),
+            format: PhantomData,
+        }
+    }
+
+    pub fn initialize<D>(&mut self, delay: &mut D) -> Result<(), DriverError<RST, BL>>
+    where
+        D: DelayNs,
+    {
+        self.initialize_with(&INIT_SEQ, delay)
     }
 
-    pub fn initialize<D>(&mut self, delay: &mut D) -> Result<(), DisplayError>
+    /// Initialize the display using a custom sequence, e.g. built with [`InitSequence`].
+    ///
+    /// The orientation, color order, inversion and pixel format configured on the driver
+    /// are applied afterwards.
+    pub fn initialize_with<D>(
+        &mut self,
+        seq: &[InitOp],
+        delay: &mut D,
+    ) -> Result<(), DriverError<RST, BL>>
     where
-        D: delay::DelayMs<u32>,
+        D: DelayNs,
     {
-        for o in INIT_SEQ {
-            match o {
+        for o in seq {
+            match *o {
                 InitOp::Cmd(c) => {
-                    self.itf.send_commands(DataFormat::U8(&[c.cmd]))?;
-                    self.itf.send_data(DataFormat::U8(c.data))?;
+                    self.write_command(c.cmd, c.data)?;
+                    self.state.track_command(c.cmd, c.data);
                 }
                 InitOp::Delay(d) => {
                     delay.delay_ms(d);
@@ -51,59 +160,561 @@ where
             }
         }
 
+        self.write_command(GC9A01A_PIXFMT, &[C::COLMOD])?;
+        self.write_inversion()?;
+        self.write_madctl()
+    }
+
+    /// Reset the display.
+    ///
+    /// Without a reset pin, a software reset is issued instead.
+    pub fn reset<D>(&mut self, delay: &mut D) -> Result<(), DriverError<RST, BL>>
+    where
+        D: DelayNs,
+    {
+        if let Some(rst) = self.rst.as_mut() {
+            rst.set_high().map_err(Error::ResetPin)?;
+            delay.delay_ms(100);
+            rst.set_low().map_err(Error::ResetPin)?;
+            delay.delay_ms(100);
+            rst.set_high().map_err(Error::ResetPin)?;
+            delay.delay_ms(100);
+        } else {
+            self.write_command(GC9A01A_SWRESET, &[])?;
+            // Sleep Out may only be sent 120ms after a software reset
+            delay.delay_ms(120);
+        }
+        self.state.reset();
+        Ok(())
+    }
+
+    /// Enter sleep mode.
+    ///
+    /// The graphics RAM content is retained, but drawing is refused until [`Self::wake`]
+    /// is called.
+    pub fn sleep<D>(&mut self, delay: &mut D) -> Result<(), DriverError<RST, BL>>
+    where
+        D: DelayNs,
+    {
+        self.write_command(GC9A01A_SLPIN, &[])?;
+        // Sleep Out may only be sent 120ms after Sleep In
+        delay.delay_ms(120);
+        self.state.sleeping = true;
         Ok(())
     }
 
-    pub fn reset<D>(&mut self, delay: &mut D) -> Result<(), DisplayError>
+    /// Leave sleep mode.
+    pub fn wake<D>(&mut self, delay: &mut D) -> Result<(), DriverError<RST, BL>>
     where
-        D: delay::DelayMs<u32>,
+        D: DelayNs,
     {
-        self.rst.set_high().map_err(|_| DisplayError::RSError)?;
-        delay.delay_ms(100);
-        self.rst.set_low().map_err(|_| DisplayError::RSError)?;
-        delay.delay_ms(100);
-        self.rst.set_high().map_err(|_| DisplayError::RSError)?;
-        delay.delay_ms(100);
+        self.write_command(GC9A01A_SLPOUT, &[])?;
+        // The supply voltages and clock need 120ms to stabilise
+        delay.delay_ms(120);
+        self.state.sleeping = false;
+        Ok(())
+    }
+
+    /// Whether the panel is in sleep mode.
+    pub fn is_sleeping(&self) -> bool {
+        self.state.sleeping
+    }
+
+    /// Turn off the panel output, showing a blank screen.
+    ///
+    /// Drawing is still possible while the display is off, the result will be shown
+    /// once [`Self::display_on`] is called.
+    pub fn display_off(&mut self) -> Result<(), DriverError<RST, BL>> {
+        self.write_command(GC9A01A_DISPOFF, &[])?;
+        self.state.display_on = false;
+        Ok(())
+    }
+
+    /// Turn on the panel output, showing the graphics RAM content.
+    pub fn display_on(&mut self) -> Result<(), DriverError<RST, BL>> {
+        self.write_command(GC9A01A_DISPON, &[])?;
+        self.state.display_on = true;
         Ok(())
     }
 
-    pub fn set_backlight(&mut self, duty: PWM::Duty) {
-        self.bl.set_duty(duty);
+    /// Whether the panel output is enabled.
+    pub fn is_display_on(&self) -> bool {
+        self.state.display_on
+    }
+
+    /// Release the display interface, reset pin and backlight.
+    ///
+    /// The reset pin is `None` for drivers created without one.
+    pub fn release(self) -> (DI, Option<RST>, BL) {
+        (self.itf, self.rst, self.bl)
     }
 
-    fn draw_color<C>(
+    /// Temporarily borrow the display interface, e.g. to talk to another device on a
+    /// shared bus.
+    ///
+    /// The driver's state (orientation, geometry, ...) stays valid, as long as `f` doesn't
+    /// send commands to the display itself.
+    pub fn with_interface<R>(&mut self, f: impl FnOnce(&mut DI) -> R) -> R {
+        f(&mut self.itf)
+    }
+
+    /// Replace the display interface, returning the previous one.
+    ///
+    /// This allows rebuilding the interface, e.g. after reconfiguring the bus clock,
+    /// without losing the driver's state.
+    pub fn replace_interface(&mut self, itf: DI) -> DI {
+        core::mem::replace(&mut self.itf, itf)
+    }
+
+    /// Configure the panel geometry.
+    ///
+    /// This is needed for modules that don't use the default 240x240 visible area,
+    /// or that map it at an offset in the controller's graphics RAM.
+    pub fn set_geometry(&mut self, geometry: Geometry) {
+        self.state.geometry = geometry;
+    }
+
+    /// Get the configured panel geometry.
+    pub fn geometry(&self) -> Geometry {
+        self.state.geometry
+    }
+
+    /// Change the display orientation.
+    ///
+    /// This only affects how subsequent drawing operations are mapped onto the panel,
+    /// the current content of the display is left untouched.
+    ///
+    /// Fails with [`Error::InvalidState`] while scrolling or in partial display mode, if
+    /// the new orientation doesn't support it, see [`Self::set_scroll_area`].
+    pub fn set_orientation(
         &mut self,
-        x_begin: u8,
-        x_end: u8,
-        y_begin: u8,
-        y_end: u8,
-        data: &mut C,
-    ) -> Result<(), DisplayError>
+        orientation: Orientation,
+    ) -> Result<(), DriverError<RST, BL>> {
+        self.state.set_orientation(orientation)?;
+        self.write_madctl()
+    }
+
+    /// Get the active display orientation.
+    pub fn orientation(&self) -> Orientation {
+        self.state.orientation
+    }
+
+    /// Set the color component order of the panel.
+    ///
+    /// Use this when red and blue appear swapped.
+    pub fn set_color_order(&mut self, color_order: ColorOrder) -> Result<(), DriverError<RST, BL>> {
+        self.state.color_order = color_order;
+        self.write_madctl()
+    }
+
+    /// Get the color component order of the panel.
+    pub fn color_order(&self) -> ColorOrder {
+        self.state.color_order
+    }
+
+    /// Turn display inversion on or off.
+    ///
+    /// Most panels need inversion on to show the correct colors, which is the default.
+    pub fn set_inverted(&mut self, inverted: bool) -> Result<(), DriverError<RST, BL>> {
+        self.state.inverted = inverted;
+        self.write_inversion()
+    }
+
+    /// Whether display inversion is on.
+    pub fn is_inverted(&self) -> bool {
+        self.state.inverted
+    }
+
+    /// Set the backlight brightness, from 0 (off) to 100 (full brightness).
+    pub fn set_backlight(&mut self, percent: u8) -> Result<(), DriverError<RST, BL>> {
+        self.bl.set_brightness(percent).map_err(Error::Backlight)
+    }
+
+    /// Define the vertical scrolling area.
+    ///
+    /// The rows between the `top_fixed` and `bottom_fixed` areas wrap around when
+    /// scrolled using [`Self::set_scroll_offset`], which is reset to 0.
+    ///
+    /// The panel scrolls its graphics RAM rows in scan order, so this is only supported
+    /// in [`Orientation::Portrait`] and [`Orientation::PortraitMirrored`]. Other
+    /// orientations fail with [`Error::InvalidState`].
+    pub fn set_scroll_area(
+        &mut self,
+        top_fixed: u16,
+        bottom_fixed: u16,
+    ) -> Result<(), DriverError<RST, BL>> {
+        if !self.state.orientation.supports_scrolling() {
+            return Err(Error::InvalidState);
+        }
+
+        let Geometry {
+            height,
+            row_offset,
+            gram_height,
+            ..
+        } = self.state.geometry;
+        let scroll_height = height
+            .checked_sub(top_fixed)
+            .and_then(|h| h.checked_sub(bottom_fixed))
+            .filter(|&h| h > 0)
+            .ok_or(Error::OutOfBounds)?;
+
+        // The fixed areas include the graphics RAM rows above and below the visible area,
+        // so that all three add up to the graphics RAM height
+        let rows_below = row_offset
+            .checked_add(height)
+            .and_then(|end| gram_height.checked_sub(end))
+            .ok_or(Error::OutOfBounds)?;
+        let [tfa_hi, tfa_lo] = (row_offset + top_fixed).to_be_bytes();
+        let [vsa_hi, vsa_lo] = scroll_height.to_be_bytes();
+        let [bfa_hi, bfa_lo] = (rows_below + bottom_fixed).to_be_bytes();
+        self.write_command(
+            GC9A01A_VSCRDEF,
+            &[tfa_hi, tfa_lo, vsa_hi, vsa_lo, bfa_hi, bfa_lo],
+        )?;
+
+        self.state.scroll = Some(ScrollArea {
+            top_fixed,
+            height: scroll_height,
+            offset: 0,
+        });
+        self.set_scroll_offset(0)
+    }
+
+    /// Scroll the area defined with [`Self::set_scroll_area`].
+    ///
+    /// `offset` is the row of the scrolling area that is shown at its top,
+    /// and must be smaller than the height of the scrolling area.
+    pub fn set_scroll_offset(&mut self, offset: u16) -> Result<(), DriverError<RST, BL>> {
+        let Some(mut scroll) = self.state.scroll else {
+            return Err(Error::InvalidState);
+        };
+        if offset >= scroll.height {
+            return Err(Error::OutOfBounds);
+        }
+
+        let [vsp_hi, vsp_lo] =
+            (self.state.geometry.row_offset + scroll.top_fixed + offset).to_be_bytes();
+        self.write_command(GC9A01A_VSCRSADD, &[vsp_hi, vsp_lo])?;
+
+        scroll.offset = offset;
+        self.state.scroll = Some(scroll);
+        Ok(())
+    }
+
+    /// Get the current scroll offset, 0 when scrolling is not in use.
+    pub fn scroll_offset(&self) -> u16 {
+        self.state.scroll.map_or(0, |scroll| scroll.offset)
+    }
+
+    /// Stop scrolling and return to normal display mode.
+    ///
+    /// This also leaves partial display mode.
+    pub fn disable_scrolling(&mut self) -> Result<(), DriverError<RST, BL>> {
+        self.write_command(GC9A01A_NORON, &[])?;
+        self.state.scroll = None;
+        self.state.partial = false;
+        Ok(())
+    }
+
+    /// Enter partial display mode, only showing the given display rows.
+    ///
+    /// The rest of the panel is left blank, which lowers power consumption for e.g.
+    /// an always-on watch face. This stops scrolling.
+    ///
+    /// Like scrolling, the partial area is defined in graphics RAM scan order, so this
+    /// is only supported in [`Orientation::Portrait`] and [`Orientation::PortraitMirrored`].
+    /// Other orientations fail with [`Error::InvalidState`].
+    pub fn enter_partial_mode(&mut self, rows: Range<u16>) -> Result<(), DriverError<RST, BL>> {
+        if !self.state.orientation.supports_scrolling() {
+            return Err(Error::InvalidState);
+        }
+        if rows.is_empty() || rows.end > self.state.geometry.height {
+            return Err(Error::OutOfBounds);
+        }
+
+        let row_offset = self.state.geometry.row_offset;
+        let [sr_hi, sr_lo] = (row_offset + rows.start).to_be_bytes();
+        let [er_hi, er_lo] = (row_offset + rows.end - 1).to_be_bytes();
+        self.write_command(GC9A01A_PTLAR, &[sr_hi, sr_lo, er_hi, er_lo])?;
+        self.write_command(GC9A01A_PTLON, &[])?;
+
+        self.state.scroll = None;
+        self.state.partial = true;
+        Ok(())
+    }
+
+    /// Leave partial display mode and show the whole panel again.
+    ///
+    /// This returns to normal display mode, so it also stops scrolling, like
+    /// [`Self::disable_scrolling`].
+    pub fn exit_partial_mode(&mut self) -> Result<(), DriverError<RST, BL>> {
+        self.disable_scrolling()
+    }
+
+    /// Whether partial display mode is active.
+    pub fn is_partial_mode(&self) -> bool {
+        self.state.partial
+    }
+
+    /// Map a display row to the graphics RAM row that is currently shown there.
+    ///
+    /// Drawing through [`DrawTarget`](embedded_graphics_core::draw_target::DrawTarget)
+    /// already applies this mapping, so content lands where it is expected while
+    /// scrolled.
+    pub fn physical_row(&self, row: u16) -> u16 {
+        self.map_row(row).0
+    }
+
+    /// Map a display row to a graphics RAM row, along with the last display row up to
+    /// which the mapping stays contiguous.
+    fn map_row(&self, row: u16) -> (u16, u16) {
+        match self.state.scroll {
+            Some(scroll) => scroll.map_row(row),
+            None => (row, u16::MAX),
+        }
+    }
+
+    /// Set the gamma curves for the positive and negative source driver polarity.
+    ///
+    /// Normally both are set to the same curve.
+    pub fn set_gamma(
+        &mut self,
+        positive: &GammaCurve,
+        negative: &GammaCurve,
+    ) -> Result<(), DriverError<RST, BL>> {
+        let (gamma1, gamma2) = positive.encode();
+        let (gamma3, gamma4) = negative.encode();
+        self.write_command(GC9A01A_GAMMA1, &gamma1)?;
+        self.write_command(GC9A01A_GAMMA2, &gamma2)?;
+        self.write_command(GC9A01A_GAMMA3, &gamma3)?;
+        self.write_command(GC9A01A_GAMMA4, &gamma4)
+    }
+
+    /// Turn idle mode on or off.
+    ///
+    /// In idle mode only 8 colors are shown, using the most significant bit of each color
+    /// channel, which lowers power consumption for e.g. an ambient display.
+    /// Draw through [`IdleColors`] to see the same colors as the display will show.
+    pub fn set_idle_mode(&mut self, idle: bool) -> Result<(), DriverError<RST, BL>> {
+        let cmd = if idle { GC9A01A_IDMON } else { GC9A01A_IDMOFF };
+        self.write_command(cmd, &[])?;
+        self.state.idle = idle;
+        Ok(())
+    }
+
+    /// Whether idle (8-color) mode is active.
+    pub fn is_idle_mode(&self) -> bool {
+        self.state.idle
+    }
+
+    /// Set the frame rate and dot inversion scheme.
+    pub fn set_frame_rate(&mut self, frame_rate: FrameRate) -> Result<(), DriverError<RST, BL>> {
+        self.write_command(GC9A01A_FRAMERATE, &[frame_rate.param()])
+    }
+
+    /// Set the number of blanking lines before (front porch) and after (back porch)
+    /// each frame, using Blanking Porch Control (B5h).
+    ///
+    /// Longer porches lower the refresh rate, and leave more time to update graphics RAM
+    /// in sync with the tearing effect signal.
+    pub fn set_blanking_porch(&mut self, front: u8, back: u8) -> Result<(), DriverError<RST, BL>> {
+        self.write_command(GC9A01A1_BLPCTL, &[front, back])
+    }
+
+    /// Enable the tearing effect (TE) output line.
+    pub fn enable_tearing_effect(
+        &mut self,
+        mode: TearingEffect,
+    ) -> Result<(), DriverError<RST, BL>> {
+        let m = match mode {
+            TearingEffect::VBlank => 0x00,
+            TearingEffect::VHBlank => 0x01,
+        };
+        self.write_command(GC9A01A_TEON, &[m])?;
+        self.state.tearing_effect = Some(mode);
+        Ok(())
+    }
+
+    /// Disable the tearing effect (TE) output line.
+    pub fn disable_tearing_effect(&mut self) -> Result<(), DriverError<RST, BL>> {
+        self.write_command(GC9A01A_TEOFF, &[])?;
+        self.state.tearing_effect = None;
+        Ok(())
+    }
+
+    /// Configure the tearing effect pulse, using Tearing Effect Control (BAh).
+    ///
+    /// `width` is the 7-bit pulse width, the pulse is active high unless `active_low` is set.
+    pub fn set_tearing_effect_pulse(
+        &mut self,
+        width: u8,
+        active_low: bool,
+    ) -> Result<(), DriverError<RST, BL>> {
+        let pol = if active_low { 0x80 } else { 0x00 };
+        self.write_command(GC9A01A1_TECTL, &[pol | (width & 0x7F)])
+    }
+
+    /// Get the tearing effect output line mode, if enabled.
+    pub fn tearing_effect(&self) -> Option<TearingEffect> {
+        self.state.tearing_effect
+    }
+
+    /// Wait for the start of vertical blanking before updating graphics RAM.
+    ///
+    /// Writing a full frame right after this avoids visible tearing, as long as the
+    /// write keeps ahead of the panel refresh. Returns immediately when the tearing effect
+    /// line is disabled, since there would be no signal to wait for.
+    pub fn wait_for_vsync<V>(&self, te: &mut V) -> Result<(), V::Error>
     where
-        C: Iterator<Item = u16>,
+        V: VsyncWait,
     {
-        self.set_windows(x_begin, x_end, y_begin, y_end)?;
-        self.itf.send_data(DataFormat::U16BEIter(data))
+        if self.state.tearing_effect.is_some() {
+            te.wait_for_vsync()
+        } else {
+            Ok(())
+        }
+    }
+
+    fn write_command(&mut self, cmd: u8, data: &[u8]) -> Result<(), DriverError<RST, BL>> {
+        self.itf.send_commands(DataFormat::U8(&[cmd]))?;
+        self.itf.send_data(DataFormat::U8(data))?;
+        Ok(())
+    }
+
+    fn write_madctl(&mut self) -> Result<(), DriverError<RST, BL>> {
+        self.write_command(GC9A01A_MADCTL, &[self.state.madctl()])
+    }
+
+    fn write_inversion(&mut self) -> Result<(), DriverError<RST, BL>> {
+        self.write_command(self.state.inversion_command(), &[])
     }
 
-    fn draw_bytes(
+    fn draw_color<I>(
         &mut self,
-        x_begin: u8,
-        x_end: u8,
-        y_begin: u8,
-        y_end: u8,
-        data: &[u8],
-    ) -> Result<(), DisplayError> {
+        x_begin: u16,
+        x_end: u16,
+        y_begin: u16,
+        y_end: u16,
+        colors: I,
+    ) -> Result<(), DriverError<RST, BL>>
+    where
+        I: Iterator<Item = C>,
+    {
         self.set_windows(x_begin, x_end, y_begin, y_end)?;
-        self.itf.send_data(DataFormat::U8(data))
+        C::send_pixels(&mut self.itf, colors)?;
+        Ok(())
+    }
+
+    /// Set the address window, in display coordinates, and start a memory write.
+    ///
+    /// Fails with [`Error::InvalidState`] while the panel is in sleep mode, and with
+    /// [`Error::OutOfBounds`] if the window doesn't fit in graphics RAM.
+    fn set_windows(
+        &mut self,
+        xs: u16,
+        xe: u16,
+        ys: u16,
+        ye: u16,
+    ) -> Result<(), DriverError<RST, BL>> {
+        let (caset, paset) = self.state.address_window(xs, xe, ys, ye)?;
+        self.write_command(GC9A01A_CASET, &caset)?;
+        self.write_command(GC9A01A_PASET, &paset)?;
+        self.itf.send_commands(DataFormat::U8(&[GC9A01A_RAMWR]))?;
+        Ok(())
     }
+}
+
+impl<DI, RST, BL, C> GC9A01A<DI, RST, BL, C>
+where
+    DI: ReadDataCommand,
+    RST: OutputPin,
+    BL: Backlight,
+    C: PixelFormat,
+{
+    /// Read the display identification.
+    ///
+    /// This can be used to detect an absent or miswired panel at boot,
+    /// see [`DisplayId::is_absent`].
+    pub fn read_id(&mut self) -> Result<DisplayId, DriverError<RST, BL>> {
+        let mut buf = [0; 3];
+        self.itf.read_data(GC9A01A_RDDID, &mut buf)?;
+        Ok(DisplayId {
+            manufacturer: buf[0],
+            version: buf[1],
+            driver: buf[2],
+        })
+    }
+
+    /// Read the display status, as the 32-bit value returned by Read Display Status.
+    pub fn read_status(&mut self) -> Result<u32, DriverError<RST, BL>> {
+        let mut buf = [0; 4];
+        self.itf.read_data(GC9A01A_RDDST, &mut buf)?;
+        Ok(u32::from_be_bytes(buf))
+    }
+
+    /// Read the power mode of the display.
+    pub fn read_power_mode(&mut self) -> Result<PowerMode, DriverError<RST, BL>> {
+        let mut buf = [0; 1];
+        self.itf.read_data(GC9A01A_RDDPM, &mut buf)?;
+        Ok(PowerMode(buf[0]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::registers::GC9A01A_PTLAR;
+    use crate::{mock, Error, Geometry, Orientation};
+
+    #[test]
+    fn partial_mode_portrait() {
+        let mut display = mock::display();
+        display.set_geometry(Geometry::new(240, 200).with_offset(0, 20));
+        assert!(matches!(
+            display.enter_partial_mode(0..220),
+            Err(Error::OutOfBounds)
+        ));
+        display.enter_partial_mode(0..40).unwrap();
+        assert!(display.is_partial_mode());
+
+        let itf = display.release().0;
+        let (_, params) = itf
+            .commands
+            .iter()
+            .find(|(cmd, _)| *cmd == GC9A01A_PTLAR)
+            .unwrap();
+        assert_eq!(params[..], [0, 20, 0, 59]);
+    }
+
+    #[test]
+    fn partial_mode_unsupported_orientations() {
+        for orientation in [Orientation::PortraitFlipped, Orientation::Landscape] {
+            let mut display = mock::display();
+            display.set_orientation(orientation).unwrap();
+            assert!(matches!(
+                display.enter_partial_mode(0..40),
+                Err(Error::InvalidState)
+            ));
+            assert!(!display.is_partial_mode());
+        }
+
+        let mut display = mock::display();
+        display.enter_partial_mode(0..40).unwrap();
+        assert!(matches!(
+            display.set_orientation(Orientation::PortraitFlipped),
+            Err(Error::InvalidState)
+        ));
+    }
+
+    #[test]
+    fn exit_partial_mode_stops_scrolling() {
+        let mut display = mock::display();
+        display.set_scroll_area(10, 10).unwrap();
+        display.set_scroll_offset(50).unwrap();
+        assert_eq!(display.physical_row(10), 60);
 
-    // TODO: extend this to u16
-    fn set_windows(&mut self, xs: u8, xe: u8, ys: u8, ye: u8) -> Result<(), DisplayError> {
-        self.itf.send_commands(DataFormat::U8(&[GC9A01A_CASET]))?;
-        self.itf.send_data(DataFormat::U8(&[0x00, xs, 0x00, xe]))?;
-        self.itf.send_commands(DataFormat::U8(&[GC9A01A_PASET]))?;
-        self.itf.send_data(DataFormat::U8(&[0x00, ys, 0x00, ye]))?;
-        self.itf.send_commands(DataFormat::U8(&[GC9A01A_RAMWR]))
+        display.exit_partial_mode().unwrap();
+        assert_eq!(display.physical_row(10), 10);
+        assert_eq!(display.scroll_offset(), 0);
     }
 }
@@ -0,0 +1,130 @@
+//! Display interface that emulates the controller's graphics RAM, for host tests.
+
+extern crate std;
+
+use std::vec;
+use std::vec::Vec;
+
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+
+use crate::registers::*;
+use crate::{NoBacklight, NoResetPin, GC9A01A};
+
+/// Graphics RAM columns and rows.
+pub const GRAM_SIZE: usize = 240;
+
+/// Initial content of graphics RAM, to tell apart pixels that were never written.
+pub const UNWRITTEN: u16 = 0xFFFF;
+
+/// Records RGB565 memory writes in graphics RAM, addressed as set by CASET/PASET.
+#[derive(Debug)]
+pub struct MockInterface {
+    pub gram: Vec<u16>,
+    /// Every command sent, with its parameters.
+    pub commands: Vec<(u8, Vec<u8>)>,
+    columns: (u16, u16),
+    rows: (u16, u16),
+    cursor: (u16, u16),
+    /// First byte of a pixel, when pixels are sent as bytes.
+    high_byte: Option<u8>,
+}
+
+impl MockInterface {
+    pub fn new() -> Self {
+        Self {
+            gram: vec![UNWRITTEN; GRAM_SIZE * GRAM_SIZE],
+            commands: Vec::new(),
+            columns: (0, 0),
+            rows: (0, 0),
+            cursor: (0, 0),
+            high_byte: None,
+        }
+    }
+
+    /// Pixel at graphics RAM column `x` and row `y`.
+    pub fn pixel(&self, x: usize, y: usize) -> u16 {
+        self.gram[y * GRAM_SIZE + x]
+    }
+
+    fn write_pixel(&mut self, pixel: u16) {
+        let (x, y) = self.cursor;
+        self.gram[usize::from(y) * GRAM_SIZE + usize::from(x)] = pixel;
+
+        // The address counter wraps around within the window
+        self.cursor = if x < self.columns.1 {
+            (x + 1, y)
+        } else if y < self.rows.1 {
+            (self.columns.0, y + 1)
+        } else {
+            (self.columns.0, self.rows.0)
+        };
+    }
+
+    fn write_byte(&mut self, byte: u8) {
+        match self.high_byte.take() {
+            Some(high) => self.write_pixel(u16::from_be_bytes([high, byte])),
+            None => self.high_byte = Some(byte),
+        }
+    }
+
+    fn set_address(&mut self, cmd: u8, data: &[u8]) {
+        let [s_hi, s_lo, e_hi, e_lo] = data.try_into().unwrap();
+        let range = (
+            u16::from_be_bytes([s_hi, s_lo]),
+            u16::from_be_bytes([e_hi, e_lo]),
+        );
+        if cmd == GC9A01A_CASET {
+            self.columns = range;
+        } else {
+            self.rows = range;
+        }
+    }
+}
+
+impl WriteOnlyDataCommand for MockInterface {
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        let DataFormat::U8(&[cmd]) = cmd else {
+            panic!("commands are sent as single bytes");
+        };
+        if cmd == GC9A01A_RAMWR {
+            self.cursor = (self.columns.0, self.rows.0);
+            self.high_byte = None;
+        }
+        self.commands.push((cmd, Vec::new()));
+        Ok(())
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        let cmd = self.commands.last().map(|(cmd, _)| *cmd);
+        if cmd != Some(GC9A01A_RAMWR) {
+            let DataFormat::U8(data) = buf else {
+                panic!("parameters are sent as bytes");
+            };
+            if let Some(cmd @ (GC9A01A_CASET | GC9A01A_PASET)) = cmd {
+                self.set_address(cmd, data);
+            }
+            if let Some((_, params)) = self.commands.last_mut() {
+                params.extend_from_slice(data);
+            }
+            return Ok(());
+        }
+
+        match buf {
+            DataFormat::U8(data) => data.iter().for_each(|&b| self.write_byte(b)),
+            DataFormat::U8Iter(iter) => iter.for_each(|b| self.write_byte(b)),
+            DataFormat::U16(data) => data
+                .iter()
+                .for_each(|&p| self.write_pixel(u16::from_be_bytes(p.to_ne_bytes()))),
+            DataFormat::U16BEIter(iter) => iter.for_each(|p| self.write_pixel(p)),
+            _ => panic!("unused data format"),
+        }
+        Ok(())
+    }
+}
+
+/// An awake driver on a [`MockInterface`].
+pub fn display() -> GC9A01A<MockInterface, NoResetPin, NoBacklight> {
+    let mut display = GC9A01A::new_without_reset(MockInterface::new(), NoBacklight);
+    display.state.sleeping = false;
+    display
+}
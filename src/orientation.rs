@@ -0,0 +1,108 @@
+use crate::registers::*;
+
+/// Display orientation, applied through the memory access control register (MADCTL).
+///
+/// The rotations are clockwise, relative to the panel's native portrait orientation.
+/// The mirrored variants additionally flip the rotated image horizontally, as seen by the
+/// viewer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Orientation {
+    /// Native orientation (0°).
+    #[default]
+    Portrait,
+    /// Rotated by 90°.
+    Landscape,
+    /// Rotated by 180°.
+    PortraitFlipped,
+    /// Rotated by 270°.
+    LandscapeFlipped,
+    /// Native orientation, mirrored horizontally.
+    PortraitMirrored,
+    /// Rotated by 90°, mirrored horizontally.
+    LandscapeMirrored,
+    /// Rotated by 180°, mirrored horizontally.
+    PortraitFlippedMirrored,
+    /// Rotated by 270°, mirrored horizontally.
+    LandscapeFlippedMirrored,
+}
+
+impl Orientation {
+    /// The MADCTL row/column exchange and address order bits for this orientation.
+    pub(crate) fn madctl(self) -> u8 {
+        match self {
+            Orientation::Portrait => MADCTL_MX,
+            Orientation::Landscape => MADCTL_MV,
+            Orientation::PortraitFlipped => MADCTL_MY,
+            Orientation::LandscapeFlipped => MADCTL_MX | MADCTL_MY | MADCTL_MV,
+            Orientation::PortraitMirrored => 0,
+            Orientation::LandscapeMirrored => MADCTL_MY | MADCTL_MV,
+            Orientation::PortraitFlippedMirrored => MADCTL_MX | MADCTL_MY,
+            Orientation::LandscapeFlippedMirrored => MADCTL_MX | MADCTL_MV,
+        }
+    }
+
+    /// Whether rows and columns are exchanged, i.e. width and height are swapped.
+    pub fn is_landscape(self) -> bool {
+        self.madctl() & MADCTL_MV != 0
+    }
+
+    /// Whether graphics RAM columns are written in reverse order (MX).
+    pub(crate) fn mirrors_columns(self) -> bool {
+        self.madctl() & MADCTL_MX != 0
+    }
+
+    /// Whether graphics RAM rows are written in reverse order (MY).
+    pub(crate) fn mirrors_rows(self) -> bool {
+        self.madctl() & MADCTL_MY != 0
+    }
+
+    /// Whether display rows are graphics RAM rows in scan order, which vertical
+    /// scrolling relies on.
+    pub(crate) fn supports_scrolling(self) -> bool {
+        !self.is_landscape() && !self.mirrors_rows()
+    }
+}
+
+/// Order of the color components in the panel, applied through MADCTL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorOrder {
+    Rgb,
+    #[default]
+    Bgr,
+}
+
+impl ColorOrder {
+    /// The MADCTL color order bit.
+    pub(crate) fn madctl(self) -> u8 {
+        match self {
+            ColorOrder::Rgb => MADCTL_RGB,
+            ColorOrder::Bgr => MADCTL_BGR,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Orientation;
+    use crate::registers::*;
+
+    #[test]
+    fn mirrored_flips_display_columns() {
+        use Orientation::*;
+
+        for (orientation, mirrored) in [
+            (Portrait, PortraitMirrored),
+            (PortraitFlipped, PortraitFlippedMirrored),
+            (Landscape, LandscapeMirrored),
+            (LandscapeFlipped, LandscapeFlippedMirrored),
+        ] {
+            // Display columns are graphics RAM rows when rows and columns are exchanged
+            let column_bit = if orientation.is_landscape() {
+                MADCTL_MY
+            } else {
+                MADCTL_MX
+            };
+            assert_eq!(orientation.madctl() ^ mirrored.madctl(), column_bit);
+        }
+    }
+}
@@ -0,0 +1,142 @@
+#[cfg(feature = "async")]
+use display_interface::AsyncWriteOnlyDataCommand;
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+
+use embedded_graphics_core::pixelcolor::{Rgb565, Rgb666, Rgb888};
+use embedded_graphics_core::prelude::*;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Color type that the display can be driven with, selecting the interface pixel format.
+///
+/// [`Rgb565`] uses the 16-bit format, [`Rgb666`] and [`Rgb888`] use the 18-bit format,
+/// which is sent as 3 bytes per pixel.
+pub trait PixelFormat: RgbColor + sealed::Sealed {
+    /// COLMOD (3Ah) parameter for this format.
+    const COLMOD: u8;
+
+    /// Number of bytes sent per pixel.
+    const BYTES_PER_PIXEL: usize;
+
+    /// Encode a pixel into the first `BYTES_PER_PIXEL` bytes of `out`.
+    fn encode(self, out: &mut [u8]);
+
+    /// Send pixel data, after a memory write has been started.
+    fn send_pixels<DI, I>(itf: &mut DI, colors: I) -> Result<(), DisplayError>
+    where
+        DI: WriteOnlyDataCommand,
+        I: Iterator<Item = Self>;
+
+    /// Send pixel data over an async interface, after a memory write has been started.
+    #[cfg(feature = "async")]
+    #[allow(async_fn_in_trait)]
+    async fn send_pixels_async<DI, I>(itf: &mut DI, colors: I) -> Result<(), DisplayError>
+    where
+        DI: AsyncWriteOnlyDataCommand,
+        I: Iterator<Item = Self>;
+}
+
+impl sealed::Sealed for Rgb565 {}
+
+impl PixelFormat for Rgb565 {
+    const COLMOD: u8 = 0x05;
+    const BYTES_PER_PIXEL: usize = 2;
+
+    fn encode(self, out: &mut [u8]) {
+        out[..2].copy_from_slice(&self.into_storage().to_be_bytes());
+    }
+
+    fn send_pixels<DI, I>(itf: &mut DI, colors: I) -> Result<(), DisplayError>
+    where
+        DI: WriteOnlyDataCommand,
+        I: Iterator<Item = Self>,
+    {
+        itf.send_data(DataFormat::U16BEIter(
+            &mut colors.map(|color| color.into_storage()),
+        ))
+    }
+
+    #[cfg(feature = "async")]
+    async fn send_pixels_async<DI, I>(itf: &mut DI, colors: I) -> Result<(), DisplayError>
+    where
+        DI: AsyncWriteOnlyDataCommand,
+        I: Iterator<Item = Self>,
+    {
+        itf.send_data(DataFormat::U16BEIter(
+            &mut colors.map(|color| color.into_storage()),
+        ))
+        .await
+    }
+}
+
+impl sealed::Sealed for Rgb666 {}
+
+impl PixelFormat for Rgb666 {
+    const COLMOD: u8 = 0x06;
+    const BYTES_PER_PIXEL: usize = 3;
+
+    fn encode(self, out: &mut [u8]) {
+        out[..3].copy_from_slice(&[self.r() << 2, self.g() << 2, self.b() << 2]);
+    }
+
+    fn send_pixels<DI, I>(itf: &mut DI, colors: I) -> Result<(), DisplayError>
+    where
+        DI: WriteOnlyDataCommand,
+        I: Iterator<Item = Self>,
+    {
+        // Each channel is sent in the upper 6 bits of a byte
+        itf.send_data(DataFormat::U8Iter(
+            &mut colors.flat_map(|color| [color.r() << 2, color.g() << 2, color.b() << 2]),
+        ))
+    }
+
+    #[cfg(feature = "async")]
+    async fn send_pixels_async<DI, I>(itf: &mut DI, colors: I) -> Result<(), DisplayError>
+    where
+        DI: AsyncWriteOnlyDataCommand,
+        I: Iterator<Item = Self>,
+    {
+        // Each channel is sent in the upper 6 bits of a byte
+        itf.send_data(DataFormat::U8Iter(
+            &mut colors.flat_map(|color| [color.r() << 2, color.g() << 2, color.b() << 2]),
+        ))
+        .await
+    }
+}
+
+impl sealed::Sealed for Rgb888 {}
+
+impl PixelFormat for Rgb888 {
+    const COLMOD: u8 = 0x06;
+    const BYTES_PER_PIXEL: usize = 3;
+
+    fn encode(self, out: &mut [u8]) {
+        out[..3].copy_from_slice(&[self.r(), self.g(), self.b()]);
+    }
+
+    fn send_pixels<DI, I>(itf: &mut DI, colors: I) -> Result<(), DisplayError>
+    where
+        DI: WriteOnlyDataCommand,
+        I: Iterator<Item = Self>,
+    {
+        // The lower 2 bits of each channel are ignored by the display
+        itf.send_data(DataFormat::U8Iter(
+            &mut colors.flat_map(|color| [color.r(), color.g(), color.b()]),
+        ))
+    }
+
+    #[cfg(feature = "async")]
+    async fn send_pixels_async<DI, I>(itf: &mut DI, colors: I) -> Result<(), DisplayError>
+    where
+        DI: AsyncWriteOnlyDataCommand,
+        I: Iterator<Item = Self>,
+    {
+        // The lower 2 bits of each channel are ignored by the display
+        itf.send_data(DataFormat::U8Iter(
+            &mut colors.flat_map(|color| [color.r(), color.g(), color.b()]),
+        ))
+        .await
+    }
+}
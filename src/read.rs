@@ -0,0 +1,232 @@
+use embedded_hal::digital::OutputPin;
+use embedded_hal::spi::{Operation, SpiDevice};
+
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+use display_interface_spi::SPIInterface;
+
+use crate::registers::*;
+
+/// Display interface that can also read data back from the display.
+///
+/// [`SpiReadInterface`] implements this for the 4-line serial interface.
+pub trait ReadDataCommand: WriteOnlyDataCommand {
+    /// Send a read command and fill `buf` with the returned parameters.
+    ///
+    /// Any dummy clock cycles between the command and the returned data are handled
+    /// by the implementation, `buf` only receives the parameters. Over serial
+    /// interfaces, Read Display Identification (04h) and Read Display Status (09h)
+    /// return their data after a single dummy clock cycle. The single byte reads, such
+    /// as Read Display Power Mode (0Ah), don't have one.
+    fn read_data(&mut self, cmd: u8, buf: &mut [u8]) -> Result<(), DisplayError>;
+}
+
+/// Whether the serial interface inserts a dummy clock cycle before the data of `cmd`.
+fn has_dummy_clock(cmd: u8) -> bool {
+    matches!(cmd, GC9A01A_RDDID | GC9A01A_RDDST)
+}
+
+/// 4-line SPI display interface that can read data back from the display.
+///
+/// This needs the data/command pin and the display's data output (SDO) connected to
+/// MISO. 3-line SPI, with the data/command bit in the data stream, isn't supported.
+///
+/// Writes behave like [`SPIInterface`]. A read is a single SPI transaction, writing the
+/// command and then reading the parameters, with the data/command pin held low.
+///
+/// The dummy clock cycle of multi-byte reads is one bit long, so one more byte is read
+/// and the data is shifted into place.
+#[derive(Debug)]
+pub struct SpiReadInterface<SPI, DC> {
+    spi: SPI,
+    dc: DC,
+}
+
+impl<SPI, DC> SpiReadInterface<SPI, DC>
+where
+    SPI: SpiDevice,
+    DC: OutputPin,
+{
+    /// Create a new SPI interface from an SPI device and the data/command pin.
+    pub fn new(spi: SPI, dc: DC) -> Self {
+        Self { spi, dc }
+    }
+
+    /// Release the SPI device and the data/command pin.
+    pub fn release(self) -> (SPI, DC) {
+        (self.spi, self.dc)
+    }
+}
+
+impl<SPI, DC> WriteOnlyDataCommand for SpiReadInterface<SPI, DC>
+where
+    SPI: SpiDevice,
+    DC: OutputPin,
+{
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        SPIInterface::new(&mut self.spi, &mut self.dc).send_commands(cmd)
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        SPIInterface::new(&mut self.spi, &mut self.dc).send_data(buf)
+    }
+}
+
+impl<SPI, DC> ReadDataCommand for SpiReadInterface<SPI, DC>
+where
+    SPI: SpiDevice,
+    DC: OutputPin,
+{
+    /// Reads up to 4 parameters, longer buffers fail with
+    /// [`DisplayError::OutOfBoundsError`]. Bus errors are reported as
+    /// [`DisplayError::BusWriteError`].
+    fn read_data(&mut self, cmd: u8, buf: &mut [u8]) -> Result<(), DisplayError> {
+        let mut raw = [0; 5];
+        let dummy = usize::from(has_dummy_clock(cmd));
+        let raw = raw
+            .get_mut(..buf.len() + dummy)
+            .filter(|_| buf.len() <= 4)
+            .ok_or(DisplayError::OutOfBoundsError)?;
+
+        self.dc.set_low().map_err(|_| DisplayError::DCError)?;
+        self.spi
+            .transaction(&mut [Operation::Write(&[cmd]), Operation::Read(raw)])
+            .map_err(|_| DisplayError::BusWriteError)?;
+
+        if dummy == 0 {
+            buf.copy_from_slice(raw);
+        } else {
+            for (i, b) in buf.iter_mut().enumerate() {
+                *b = raw[i] << 1 | raw[i + 1] >> 7;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Display identification, as returned by Read Display Identification (04h).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayId {
+    /// LCD module manufacturer ID.
+    pub manufacturer: u8,
+    /// LCD module/driver version ID.
+    pub version: u8,
+    /// LCD module/driver ID.
+    pub driver: u8,
+}
+
+impl DisplayId {
+    /// Whether the ID looks like a floating or shorted data line, i.e. no panel responded.
+    pub fn is_absent(&self) -> bool {
+        let all = [self.manufacturer, self.version, self.driver];
+        all == [0x00; 3] || all == [0xFF; 3]
+    }
+}
+
+/// Power mode, as returned by Read Display Power Mode (0Ah).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PowerMode(pub u8);
+
+impl PowerMode {
+    /// Booster voltage status.
+    pub fn booster_on(&self) -> bool {
+        self.0 & 0x80 != 0
+    }
+
+    /// Idle mode on.
+    pub fn idle_mode(&self) -> bool {
+        self.0 & 0x40 != 0
+    }
+
+    /// Partial mode on.
+    pub fn partial_mode(&self) -> bool {
+        self.0 & 0x20 != 0
+    }
+
+    /// Sleep mode off.
+    pub fn sleep_out(&self) -> bool {
+        self.0 & 0x10 != 0
+    }
+
+    /// Normal display mode on.
+    pub fn normal_mode(&self) -> bool {
+        self.0 & 0x08 != 0
+    }
+
+    /// Display on.
+    pub fn display_on(&self) -> bool {
+        self.0 & 0x04 != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+
+    use embedded_hal::digital::{self, OutputPin};
+    use embedded_hal::spi::{self, Operation, SpiDevice};
+
+    use super::{ReadDataCommand, SpiReadInterface};
+    use crate::registers::*;
+
+    /// SPI device that answers reads with the bytes of `response`.
+    struct MockSpi {
+        response: [u8; 5],
+        written: Option<u8>,
+    }
+
+    impl spi::ErrorType for MockSpi {
+        type Error = Infallible;
+    }
+
+    impl SpiDevice for MockSpi {
+        fn transaction(&mut self, ops: &mut [Operation<'_, u8>]) -> Result<(), Infallible> {
+            for op in ops {
+                match op {
+                    Operation::Write(&[cmd]) => self.written = Some(cmd),
+                    Operation::Read(buf) => buf.copy_from_slice(&self.response[..buf.len()]),
+                    _ => panic!("unexpected operation"),
+                }
+            }
+            Ok(())
+        }
+    }
+
+    struct MockPin;
+
+    impl digital::ErrorType for MockPin {
+        type Error = Infallible;
+    }
+
+    impl OutputPin for MockPin {
+        fn set_low(&mut self) -> Result<(), Infallible> {
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Infallible> {
+            Ok(())
+        }
+    }
+
+    fn interface(response: [u8; 5]) -> SpiReadInterface<MockSpi, MockPin> {
+        let spi = MockSpi {
+            response,
+            written: None,
+        };
+        SpiReadInterface::new(spi, MockPin)
+    }
+
+    #[test]
+    fn read_skips_dummy_clock() {
+        // 0x00, 0x9A, 0x01 after a dummy bit
+        let mut itf = interface([0x00, 0x4D, 0x00, 0x80, 0x00]);
+        let mut id = [0; 3];
+        itf.read_data(GC9A01A_RDDID, &mut id).unwrap();
+        assert_eq!(itf.spi.written, Some(GC9A01A_RDDID));
+        assert_eq!(id, [0x00, 0x9A, 0x01]);
+
+        let mut itf = interface([0x9C, 0, 0, 0, 0]);
+        let mut mode = [0];
+        itf.read_data(GC9A01A_RDDPM, &mut mode).unwrap();
+        assert_eq!(mode, [0x9C]);
+    }
+}
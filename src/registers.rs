@@ -4,9 +4,11 @@
 pub const GC9A01A_SWRESET: u8 = 0x01;
 
 ///< Read display identification information
-// const GC9A01A: u8 = 0x04;
+pub const GC9A01A_RDDID: u8 = 0x04;
 ///< Read Display Status
-// const GC9A01A: u8 = 0x09;
+pub const GC9A01A_RDDST: u8 = 0x09;
+///< Read Display Power Mode
+pub const GC9A01A_RDDPM: u8 = 0x0A;
 
 ///< Enter Sleep Mode
 pub const GC9A01A_SLPIN: u8 = 0x10;
@@ -45,6 +47,10 @@ pub const GC9A01A_TEON: u8 = 0x35;
 pub const GC9A01A_MADCTL: u8 = 0x36;
 ///< Vertical Scrolling Start Address
 pub const GC9A01A_VSCRSADD: u8 = 0x37;
+///< Idle Mode OFF
+pub const GC9A01A_IDMOFF: u8 = 0x38;
+///< Idle Mode ON
+pub const GC9A01A_IDMON: u8 = 0x39;
 ///< COLMOD: Pixel Format Set
 pub const GC9A01A_PIXFMT: u8 = 0x3A;
 
@@ -105,16 +111,25 @@ pub const MADCTL_BGR: u8 = 0x08;
 ///< LCD refresh right to left
 pub const MADCTL_MH: u8 = 0x04;
 
+/// Command sent during initialization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct InitCmd {
+    /// Command opcode.
     pub cmd: u8,
+    /// Parameters sent after the opcode.
     pub data: &'static [u8],
 }
 
+/// Step of an initialization sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InitOp {
+    /// Send a command.
     Cmd(InitCmd),
+    /// Wait for the given number of milliseconds.
     Delay(u32),
 }
 
+/// Default initialization sequence.
 pub const INIT_SEQ: [InitOp; 52] = [
     InitOp::Cmd(InitCmd {
         cmd: GC9A01A_INREGEN2,
@@ -0,0 +1,104 @@
+/// Vertical scroll definition, in display rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ScrollArea {
+    /// Number of rows fixed at the top of the display.
+    pub top_fixed: u16,
+    /// Number of rows in the scrolling area.
+    pub height: u16,
+    /// Row of the scrolling area that is shown at its top.
+    pub offset: u16,
+}
+
+impl ScrollArea {
+    /// Map a display row to the graphics RAM row that is shown there.
+    ///
+    /// Also returns the last display row up to which the mapping stays contiguous,
+    /// so a window spanning the wrap-around point can be split.
+    pub fn map_row(&self, row: u16) -> (u16, u16) {
+        let scroll_end = self.top_fixed + self.height;
+        if row < self.top_fixed {
+            (row, self.top_fixed - 1)
+        } else if row < scroll_end {
+            let physical = (row - self.top_fixed + self.offset) % self.height;
+            (
+                self.top_fixed + physical,
+                (row + (self.height - 1 - physical)).min(scroll_end - 1),
+            )
+        } else {
+            (row, u16::MAX)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::pixelcolor::Rgb565;
+    use embedded_graphics_core::prelude::*;
+
+    use super::ScrollArea;
+    use crate::registers::GC9A01A_VSCRDEF;
+    use crate::{mock, Error, Geometry, Orientation};
+
+    #[test]
+    fn map_row_stops_at_scroll_area_end() {
+        let scroll = ScrollArea {
+            top_fixed: 10,
+            height: 220,
+            offset: 50,
+        };
+        assert_eq!(scroll.map_row(5), (5, 9));
+        assert_eq!(scroll.map_row(10), (60, 179));
+        assert_eq!(scroll.map_row(180), (10, 229));
+        assert_eq!(scroll.map_row(200), (30, 229));
+        assert_eq!(scroll.map_row(230), (230, u16::MAX));
+    }
+
+    #[test]
+    fn fill_scrolled_display() {
+        let mut display = mock::display();
+        display.set_scroll_area(10, 10).unwrap();
+        display.set_scroll_offset(50).unwrap();
+        display.clear(Rgb565::BLACK).unwrap();
+
+        let itf = display.release().0;
+        assert!(itf.gram.iter().all(|&p| p == 0));
+    }
+
+    #[test]
+    fn unsupported_orientations() {
+        let mut display = mock::display();
+        display
+            .set_orientation(Orientation::PortraitFlipped)
+            .unwrap();
+        assert!(matches!(
+            display.set_scroll_area(10, 10),
+            Err(Error::InvalidState)
+        ));
+
+        display
+            .set_orientation(Orientation::PortraitMirrored)
+            .unwrap();
+        display.set_scroll_area(10, 10).unwrap();
+        assert!(matches!(
+            display.set_orientation(Orientation::Landscape),
+            Err(Error::InvalidState)
+        ));
+        assert_eq!(display.orientation(), Orientation::PortraitMirrored);
+    }
+
+    #[test]
+    fn scroll_area_covers_gram() {
+        let mut display = mock::display();
+        display.set_geometry(Geometry::new(240, 200).with_offset(0, 20));
+        display.set_scroll_area(10, 10).unwrap();
+
+        let itf = display.release().0;
+        let (_, params) = itf
+            .commands
+            .iter()
+            .find(|(cmd, _)| *cmd == GC9A01A_VSCRDEF)
+            .unwrap();
+        // 30 + 180 + 30 rows
+        assert_eq!(params[..], [0, 30, 0, 180, 0, 30]);
+    }
+}
@@ -0,0 +1,161 @@
+use embedded_graphics_core::geometry::Size;
+
+use crate::registers::*;
+use crate::scroll::ScrollArea;
+use crate::{ColorOrder, Error, Geometry, Orientation, TearingEffect};
+
+/// Display configuration and the panel state tracked by the driver.
+///
+/// This is shared by the blocking and the async driver, which only differ in how they
+/// talk to the display.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct State {
+    /// Panel geometry.
+    pub geometry: Geometry,
+    /// Active display orientation.
+    pub orientation: Orientation,
+    /// Color component order of the panel.
+    pub color_order: ColorOrder,
+    /// Whether display inversion is on.
+    pub inverted: bool,
+    /// Whether the panel is in sleep mode.
+    pub sleeping: bool,
+    /// Whether the panel output is enabled.
+    pub display_on: bool,
+    /// Vertical scroll definition, if scrolling is in use.
+    pub scroll: Option<ScrollArea>,
+    /// Whether partial display mode is active.
+    pub partial: bool,
+    /// Whether idle (8-color) mode is active.
+    pub idle: bool,
+    /// Tearing effect output line mode, if enabled.
+    pub tearing_effect: Option<TearingEffect>,
+}
+
+impl State {
+    /// State of a panel that has just been powered up.
+    pub fn new() -> Self {
+        Self {
+            geometry: Geometry::default(),
+            orientation: Orientation::default(),
+            color_order: ColorOrder::default(),
+            inverted: true,
+            sleeping: true,
+            display_on: false,
+            scroll: None,
+            partial: false,
+            idle: false,
+            tearing_effect: None,
+        }
+    }
+
+    /// Return the panel state to its defaults after a reset, keeping the configuration.
+    pub fn reset(&mut self) {
+        *self = Self {
+            geometry: self.geometry,
+            orientation: self.orientation,
+            color_order: self.color_order,
+            inverted: self.inverted,
+            ..Self::new()
+        };
+    }
+
+    /// Keep track of the state changed by commands in an initialization sequence.
+    pub fn track_command(&mut self, cmd: u8, data: &[u8]) {
+        match cmd {
+            GC9A01A_SLPIN => self.sleeping = true,
+            GC9A01A_SLPOUT => self.sleeping = false,
+            GC9A01A_DISPOFF => self.display_on = false,
+            GC9A01A_DISPON => self.display_on = true,
+            GC9A01A_IDMOFF => self.idle = false,
+            GC9A01A_IDMON => self.idle = true,
+            GC9A01A_TEOFF => self.tearing_effect = None,
+            GC9A01A_TEON => {
+                self.tearing_effect = match data.first() {
+                    Some(m) if m & 0x01 != 0 => Some(TearingEffect::VHBlank),
+                    _ => Some(TearingEffect::VBlank),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Change the orientation, unless scrolling or partial mode rely on the current one.
+    pub fn set_orientation<RstE, BlE>(
+        &mut self,
+        orientation: Orientation,
+    ) -> Result<(), Error<RstE, BlE>> {
+        let row_mapped = self.scroll.is_some() || self.partial;
+        if row_mapped && !orientation.supports_scrolling() {
+            return Err(Error::InvalidState);
+        }
+        self.orientation = orientation;
+        Ok(())
+    }
+
+    /// The MADCTL parameter for the orientation and color order.
+    pub fn madctl(&self) -> u8 {
+        self.orientation.madctl() | self.color_order.madctl()
+    }
+
+    /// INVON or INVOFF, depending on the inversion setting.
+    pub fn inversion_command(&self) -> u8 {
+        if self.inverted {
+            GC9A01A_INVON
+        } else {
+            GC9A01A_INVOFF
+        }
+    }
+
+    /// Visible size in the active orientation.
+    pub fn size(&self) -> Size {
+        self.geometry.size(self.orientation)
+    }
+
+    /// CASET and PASET parameters for a window in display coordinates.
+    ///
+    /// Fails with [`Error::InvalidState`] while the panel is in sleep mode, and with
+    /// [`Error::OutOfBounds`] if the window doesn't fit in graphics RAM.
+    pub fn address_window<RstE, BlE>(
+        &self,
+        xs: u16,
+        xe: u16,
+        ys: u16,
+        ye: u16,
+    ) -> Result<([u8; 4], [u8; 4]), Error<RstE, BlE>> {
+        if self.sleeping {
+            return Err(Error::InvalidState);
+        }
+        self.geometry
+            .address_window(self.orientation, xs, xe, ys, ye)
+            .ok_or(Error::OutOfBounds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::State;
+    use crate::registers::*;
+    use crate::{InitOp, Orientation, TearingEffect, INIT_SEQ};
+
+    #[test]
+    fn reset_keeps_configuration() {
+        let mut state = State::new();
+        state.orientation = Orientation::Landscape;
+        state.inverted = false;
+        for op in INIT_SEQ {
+            if let InitOp::Cmd(c) = op {
+                state.track_command(c.cmd, c.data);
+            }
+        }
+        state.track_command(GC9A01A_TEON, &[0x01]);
+        assert!(!state.sleeping);
+        assert_eq!(state.tearing_effect, Some(TearingEffect::VHBlank));
+
+        state.reset();
+        assert!(state.sleeping);
+        assert_eq!(state.tearing_effect, None);
+        assert_eq!(state.orientation, Orientation::Landscape);
+        assert_eq!(state.inversion_command(), GC9A01A_INVOFF);
+    }
+}
@@ -0,0 +1,50 @@
+use embedded_hal::digital::InputPin;
+
+/// Tearing effect output line mode, selected with TEON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TearingEffect {
+    /// Only signal vertical blanking.
+    #[default]
+    VBlank,
+    /// Signal both vertical and horizontal blanking.
+    VHBlank,
+}
+
+/// Wait for the start of the vertical blanking period.
+///
+/// Implemented for [`TePin`], to busy-wait on the display's TE output, and for closures,
+/// e.g. to wait for an edge interrupt.
+pub trait VsyncWait {
+    type Error;
+
+    fn wait_for_vsync(&mut self) -> Result<(), Self::Error>;
+}
+
+/// GPIO connected to the tearing effect (TE) output of the display.
+#[derive(Debug)]
+pub struct TePin<P>(pub P);
+
+impl<P> VsyncWait for TePin<P>
+where
+    P: InputPin,
+{
+    type Error = P::Error;
+
+    fn wait_for_vsync(&mut self) -> Result<(), Self::Error> {
+        // Don't start halfway through a blanking period, wait for the next rising edge
+        while self.0.is_high()? {}
+        while self.0.is_low()? {}
+        Ok(())
+    }
+}
+
+impl<F, E> VsyncWait for F
+where
+    F: FnMut() -> Result<(), E>,
+{
+    type Error = E;
+
+    fn wait_for_vsync(&mut self) -> Result<(), Self::Error> {
+        self()
+    }
+}
@@ -0,0 +1,47 @@
+/// Dot inversion scheme used when driving the panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Inversion {
+    Column,
+    OneDot,
+    TwoDot,
+    FourDot,
+    EightDot,
+}
+
+/// Frame rate control (E8h) setting.
+///
+/// The frame rate is derived from the number of clocks per line, `rtn`: larger values
+/// lower the refresh rate, which also lowers the panel current.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameRate {
+    /// Dot inversion scheme.
+    pub inversion: Inversion,
+    /// Clocks per line selection, 4 bits.
+    pub rtn: u8,
+}
+
+impl FrameRate {
+    /// The setting used by the default initialization sequence.
+    pub const DEFAULT: Self = Self {
+        inversion: Inversion::FourDot,
+        rtn: 0x4,
+    };
+
+    /// The Frame rate control parameter.
+    pub(crate) fn param(&self) -> u8 {
+        let dinv = match self.inversion {
+            Inversion::Column => 0x0,
+            Inversion::OneDot => 0x1,
+            Inversion::TwoDot => 0x2,
+            Inversion::FourDot => 0x3,
+            Inversion::EightDot => 0x4,
+        };
+        dinv << 4 | (self.rtn & 0x0F)
+    }
+}
+
+impl Default for FrameRate {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}